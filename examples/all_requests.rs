@@ -53,6 +53,7 @@ async fn main() -> blockfrost::Result<()> {
     let assets_transactions = api.assets_transactions(&asset).await;
     let assets_addresses = api.assets_addresses(&asset).await;
     let assets_policy_by_id = api.assets_policy_by_id(policy_id).await;
+    let addresses_utxos_asset = api.addresses_utxos_asset(address, &asset).await;
 
     // Epochs
     let epoch = 225;
@@ -109,11 +110,20 @@ async fn main() -> blockfrost::Result<()> {
     let script_hash = "e1457a0c47dfb7a2f6b8fbb059bdceab163c05d34f195b87b9f2b30e";
     let scripts = api.scripts().await;
     let scripts_by_id = api.scripts_by_id(script_hash).await;
+    let scripts_json = api.scripts_json(script_hash).await;
+    let scripts_cbor = api.scripts_cbor(script_hash).await;
     let scripts_redeemers = api.scripts_redeemers(script_hash).await;
+    let datum_hash = "db583ad85881a96c73fbb26ab9e24d1120bb38f45385664bb9c797a2ea8d9a2d";
+    let scripts_datum = api.scripts_datum(datum_hash).await;
+    let scripts_datum_cbor = api.scripts_datum_cbor(datum_hash).await;
+
+    // Mempool
+    let mempool = api.mempool().await;
 
     // Transactions
     let transaction_hash = "cb0e7b0cd0f0edbe5c6e260c369f5f0f0069cd41f501243ab67f1052040de28f";
     let transaction_by_hash = api.transaction_by_hash(transaction_hash).await;
+    let transactions_cbor = api.transactions_cbor(transaction_hash).await;
     let transactions_utxos = api.transactions_utxos(transaction_hash).await;
     let transactions_stakes = api.transactions_stakes(transaction_hash).await;
     let transactions_delegations = api.transactions_delegations(transaction_hash).await;
@@ -158,6 +168,7 @@ async fn main() -> blockfrost::Result<()> {
     println!("assets_transactions: {:#?}", assets_transactions);
     println!("assets_addresses: {:#?}", assets_addresses);
     println!("assets_policy_by_id: {:#?}", assets_policy_by_id);
+    println!("addresses_utxos_asset: {:#?}", addresses_utxos_asset);
     println!("epochs_latest: {:#?}", epochs_latest);
     println!("epochs_latest_parameters: {:#?}", epochs_latest_parameters);
     println!("epochs_by_number: {:#?}", epochs_by_number);
@@ -194,8 +205,14 @@ async fn main() -> blockfrost::Result<()> {
     println!("network: {:#?}", network);
     println!("scripts: {:#?}", scripts);
     println!("scripts_by_id: {:#?}", scripts_by_id);
+    println!("scripts_json: {:#?}", scripts_json);
+    println!("scripts_cbor: {:#?}", scripts_cbor);
     println!("scripts_redeemers: {:#?}", scripts_redeemers);
+    println!("scripts_datum: {:#?}", scripts_datum);
+    println!("scripts_datum_cbor: {:#?}", scripts_datum_cbor);
+    println!("mempool: {:#?}", mempool);
     println!("transaction_by_hash: {:#?}", transaction_by_hash);
+    println!("transactions_cbor: {:#?}", transactions_cbor);
     println!("transactions_utxos: {:#?}", transactions_utxos);
     println!("transactions_stakes: {:#?}", transactions_stakes);
     println!("transactions_delegations: {:#?}", transactions_delegations);
@@ -11,7 +11,7 @@ fn build_api() -> blockfrost::Result<BlockFrostApi> {
 async fn main() -> blockfrost::Result<()> {
     let api = build_api()?;
 
-    // Should contain the correct cbor contents
+    // CBOR bytes, hex, or the contents of a `cardano-cli` TextEnvelope file
     let transaction_data = vec![0; 1024]; // Just an example (will fail)
     let transaction_hash = api.transactions_submit(transaction_data).await?;
 
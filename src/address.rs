@@ -0,0 +1,427 @@
+//! Decoding of Cardano addresses, as specified by [CIP-19].
+//!
+//! See [`DecodedAddress`].
+//!
+//! [CIP-19]: https://cips.cardano.org/cips/cip19/
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    cbor::Decoder,
+    utils::{crc32, decode_bech32, encode_bech32, encode_hex},
+    AddressUtxo, ParseError, StakeAddress, TransactionUtxosInput, TransactionUtxosOutput,
+};
+
+/// Network ID of the Cardano mainnet, testnets use `0`.
+pub const MAINNET_NETWORK_ID: u8 = 1;
+
+/// Kind of an address, from its header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressKind {
+    /// Payment and stake credentials.
+    Base,
+    /// Payment credential and a pointer to a stake registration certificate.
+    Pointer,
+    /// Payment credential only.
+    Enterprise,
+    /// Stake credential only, also known as stake address.
+    Reward,
+    /// Legacy Byron address.
+    Byron,
+}
+
+/// Payment or stake credential: a hash of a verification key or of a script.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Credential {
+    /// Hex-encoded Blake2b-224 hash of a verification key.
+    Key(String),
+    /// Hex-encoded hash of a script.
+    Script(String),
+}
+
+impl Credential {
+    fn new(is_script: bool, hash: &[u8]) -> Self {
+        if is_script {
+            Credential::Script(encode_hex(hash))
+        } else {
+            Credential::Key(encode_hex(hash))
+        }
+    }
+
+    /// Hex-encoded hash of the credential.
+    pub fn hash(&self) -> &str {
+        match self {
+            Credential::Key(hash) | Credential::Script(hash) => hash,
+        }
+    }
+
+    /// Whether this is a script credential.
+    pub fn is_script(&self) -> bool {
+        matches!(self, Credential::Script(_))
+    }
+}
+
+/// Location of the stake registration certificate of a [`AddressKind::Pointer`] address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pointer {
+    pub slot: u64,
+    pub tx_index: u64,
+    pub cert_index: u64,
+}
+
+/// Components of a decoded address.
+///
+/// Decodes bech32 Shelley addresses, bech32 stake addresses and base58 Byron addresses.
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::address::{AddressKind, DecodedAddress};
+///
+/// let address: DecodedAddress = "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(address.kind, AddressKind::Base);
+/// assert!(!address.is_script());
+/// assert_eq!(
+///     address.reward_address().unwrap(),
+///     "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecodedAddress {
+    /// Kind of the address.
+    pub kind: AddressKind,
+    /// Network ID, [`MAINNET_NETWORK_ID`] for mainnet, `0` for testnets.
+    ///
+    /// For Byron addresses, it's `0` when the address carries a protocol magic.
+    pub network_id: u8,
+    /// Payment credential, `None` for reward and Byron addresses.
+    pub payment: Option<Credential>,
+    /// Stake credential, present for base and reward addresses.
+    pub stake: Option<Credential>,
+    /// Stake pointer, present for pointer addresses.
+    pub pointer: Option<Pointer>,
+    /// Raw bytes of the address.
+    pub bytes: Vec<u8>,
+}
+
+impl DecodedAddress {
+    /// Decode an address from its bech32 or base58 form.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let error = |reason: String| ParseError::new("address", format!("'{}' {}", text, reason));
+
+        let (hrp, bytes) = match decode_bech32(text) {
+            Ok(decoded) => decoded,
+            Err(reason) if text.starts_with("addr") || text.starts_with("stake") => {
+                return Err(error(format!("is not bech32: {}", reason)));
+            }
+            Err(_) => return decode_byron(text).map_err(error),
+        };
+
+        let address = Self::decode_shelley(bytes).map_err(error)?;
+        let expected_hrp = address.hrp();
+        if hrp != expected_hrp {
+            return Err(error(format!("has prefix '{}', expected '{}'", hrp, expected_hrp)));
+        }
+        Ok(address)
+    }
+
+    /// Decode a Shelley address from its raw bytes.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, ParseError> {
+        let hex = crate::utils::encode_hex(&bytes);
+        Self::decode_shelley(bytes)
+            .map_err(|reason| ParseError::new("address", format!("'{}' {}", hex, reason)))
+    }
+
+    fn decode_shelley(bytes: Vec<u8>) -> Result<Self, String> {
+        let header = *bytes.first().ok_or("is empty")?;
+        let (address_type, network_id) = (header >> 4, header & 0x0f);
+        let payment_is_script = address_type & 0b0001 != 0;
+        let stake_is_script = address_type & 0b0010 != 0;
+
+        let expect_length = |length: usize| {
+            if bytes.len() == length {
+                Ok(())
+            } else {
+                Err(format!("has {} bytes, expected {}", bytes.len(), length))
+            }
+        };
+
+        let (kind, payment, stake, pointer) = match address_type {
+            0b0000..=0b0011 => {
+                expect_length(57)?;
+                let payment = Credential::new(payment_is_script, &bytes[1..29]);
+                let stake = Credential::new(stake_is_script, &bytes[29..57]);
+                (AddressKind::Base, Some(payment), Some(stake), None)
+            }
+            0b0100 | 0b0101 => {
+                if bytes.len() < 32 {
+                    return Err("is too short for a pointer address".to_owned());
+                }
+                let payment = Credential::new(payment_is_script, &bytes[1..29]);
+                let pointer = decode_pointer(&bytes[29..])?;
+                (AddressKind::Pointer, Some(payment), None, Some(pointer))
+            }
+            0b0110 | 0b0111 => {
+                expect_length(29)?;
+                let payment = Credential::new(payment_is_script, &bytes[1..29]);
+                (AddressKind::Enterprise, Some(payment), None, None)
+            }
+            0b1110 | 0b1111 => {
+                expect_length(29)?;
+                let stake = Credential::new(payment_is_script, &bytes[1..29]);
+                (AddressKind::Reward, None, Some(stake), None)
+            }
+            _ => return Err(format!("has unknown header type {:#06b}", address_type)),
+        };
+
+        Ok(Self { kind, network_id, payment, stake, pointer, bytes })
+    }
+
+    /// Whether the address belongs to the mainnet.
+    pub fn is_mainnet(&self) -> bool {
+        self.network_id == MAINNET_NETWORK_ID
+    }
+
+    /// Whether funds are locked by a script, that is, the payment credential is a script.
+    pub fn is_script(&self) -> bool {
+        self.payment.as_ref().is_some_and(Credential::is_script)
+    }
+
+    /// Bech32 human-readable prefix of the address, empty for Byron addresses.
+    pub fn hrp(&self) -> &'static str {
+        match (self.kind, self.is_mainnet()) {
+            (AddressKind::Byron, _) => "",
+            (AddressKind::Reward, true) => "stake",
+            (AddressKind::Reward, false) => "stake_test",
+            (_, true) => "addr",
+            (_, false) => "addr_test",
+        }
+    }
+
+    /// The stake (reward) address controlling this address, present for base and reward
+    /// addresses.
+    ///
+    /// Useful for grouping addresses and UTXOs by owner.
+    pub fn reward_address(&self) -> Option<StakeAddress> {
+        let stake = self.stake.as_ref()?;
+        let header = 0b1110_0000 | (stake.is_script() as u8) << 4 | self.network_id;
+        let hrp = if self.is_mainnet() { "stake" } else { "stake_test" };
+
+        let mut bytes = vec![header];
+        bytes.extend(crate::utils::decode_hex(stake.hash())?);
+        encode_bech32(hrp, &bytes).parse().ok()
+    }
+}
+
+impl FromStr for DecodedAddress {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+/// Encodes Shelley addresses in bech32 and Byron addresses in base58.
+impl fmt::Display for DecodedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            AddressKind::Byron => write!(f, "{}", bs58::encode(&self.bytes).into_string()),
+            _ => write!(f, "{}", encode_bech32(self.hrp(), &self.bytes)),
+        }
+    }
+}
+
+// Pointers are three variable-length naturals, 7 bits per byte, most significant first.
+fn decode_pointer(bytes: &[u8]) -> Result<Pointer, String> {
+    let mut numbers = [0u64; 3];
+    let mut iter = bytes.iter();
+
+    for number in &mut numbers {
+        loop {
+            let byte = iter.next().ok_or("has a truncated pointer")?;
+            *number = number
+                .checked_mul(128)
+                .map(|number| number | (byte & 0x7f) as u64)
+                .ok_or("has a pointer that overflows")?;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+    if iter.next().is_some() {
+        return Err("has trailing bytes after the pointer".to_owned());
+    }
+
+    let [slot, tx_index, cert_index] = numbers;
+    Ok(Pointer { slot, tx_index, cert_index })
+}
+
+// Byron addresses are base58 encoded CBOR: [#6.24(bytes .cbor [root, attributes, type]), crc32]
+fn decode_byron(text: &str) -> Result<DecodedAddress, String> {
+    let bytes = bs58::decode(text).into_vec().map_err(|_| "is not base58 nor bech32")?;
+    let invalid = |reason: ParseError| format!("is not a valid Byron address: {}", reason.reason);
+
+    let mut decoder = Decoder::new(&bytes);
+    if decoder.array().map_err(invalid)? != Some(2) || decoder.tag().map_err(invalid)? != 24 {
+        return Err("is not a valid Byron address: unexpected structure".to_owned());
+    }
+    let payload = decoder.bytes().map_err(invalid)?;
+    let checksum = decoder.unsigned().map_err(invalid)?;
+    if !decoder.is_finished() {
+        return Err("is not a valid Byron address: trailing bytes".to_owned());
+    }
+    if crc32(&payload) as u64 != checksum {
+        return Err("is not a valid Byron address: checksum mismatch".to_owned());
+    }
+
+    // Attribute 2 is the protocol magic, only present for testnets
+    let mut decoder = Decoder::new(&payload);
+    let mut has_protocol_magic = false;
+    if decoder.array().map_err(invalid)? != Some(3) {
+        return Err("is not a valid Byron address: unexpected structure".to_owned());
+    }
+    decoder.skip().map_err(invalid)?;
+    let attributes = decoder.map().map_err(invalid)?;
+    let mut index = 0;
+    while decoder.has_next(attributes, index).map_err(invalid)? {
+        has_protocol_magic |= decoder.unsigned().map_err(invalid)? == 2;
+        decoder.skip().map_err(invalid)?;
+        index += 1;
+    }
+
+    Ok(DecodedAddress {
+        kind: AddressKind::Byron,
+        network_id: if has_protocol_magic { 0 } else { MAINNET_NETWORK_ID },
+        payment: None,
+        stake: None,
+        pointer: None,
+        bytes,
+    })
+}
+
+// Defines `decoded_address` for response types with an `address` field.
+macro_rules! decoded_address_accessors {
+    ($($type:ty),*) => {
+        $(
+            impl $type {
+                #[doc = concat!(
+                    "Decode the [`address`](", stringify!($type), "::address) field, see [`DecodedAddress`]."
+                )]
+                pub fn decoded_address(&self) -> Result<DecodedAddress, ParseError> {
+                    self.address.parse()
+                }
+            }
+        )*
+    };
+}
+
+decoded_address_accessors!(
+    AddressUtxo,
+    TransactionUtxosInput,
+    TransactionUtxosOutput,
+    crate::Address
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYMENT_KEY: &str = "9493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e";
+    const STAKE_KEY: &str = "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251";
+    const SCRIPT: &str = "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f";
+
+    fn key(hash: &str) -> Option<Credential> {
+        Some(Credential::Key(hash.to_owned()))
+    }
+
+    fn script(hash: &str) -> Option<Credential> {
+        Some(Credential::Script(hash.to_owned()))
+    }
+
+    // Test vectors from CIP-19
+    #[test]
+    fn test_shelley_addresses() {
+        let cases = [
+            ("addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x", AddressKind::Base, key(PAYMENT_KEY), key(STAKE_KEY)),
+            ("addr1z8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gten0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs9yc0hh", AddressKind::Base, script(SCRIPT), key(STAKE_KEY)),
+            ("addr1yx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzerkr0vd4msrxnuwnccdxlhdjar77j6lg0wypcc9uar5d2shs2z78ve", AddressKind::Base, key(PAYMENT_KEY), script(SCRIPT)),
+            ("addr1x8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gt7r0vd4msrxnuwnccdxlhdjar77j6lg0wypcc9uar5d2shskhj42g", AddressKind::Base, script(SCRIPT), script(SCRIPT)),
+            ("addr1gx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrzqf96k", AddressKind::Pointer, key(PAYMENT_KEY), None),
+            ("addr128phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtupnz75xxcrtw79hu", AddressKind::Pointer, script(SCRIPT), None),
+            ("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8", AddressKind::Enterprise, key(PAYMENT_KEY), None),
+            ("addr1w8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcyjy7wx", AddressKind::Enterprise, script(SCRIPT), None),
+            ("stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw", AddressKind::Reward, None, key(STAKE_KEY)),
+            ("stake178phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcccycj5", AddressKind::Reward, None, script(SCRIPT)),
+        ];
+
+        for (text, kind, payment, stake) in cases.clone() {
+            let address: DecodedAddress = text.parse().unwrap();
+            assert_eq!(address.kind, kind, "{}", text);
+            assert_eq!(address.payment, payment, "{}", text);
+            assert_eq!(address.stake, stake, "{}", text);
+            assert!(address.is_mainnet());
+            assert_eq!(address.to_string(), text);
+        }
+
+        let pointer: DecodedAddress = cases[4].0.parse().unwrap();
+        let expected = Pointer { slot: 2498243, tx_index: 27, cert_index: 3 };
+        assert_eq!(pointer.pointer, Some(expected));
+    }
+
+    #[test]
+    fn test_reward_address() {
+        let base: DecodedAddress = "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs68faae"
+            .parse()
+            .unwrap();
+        assert_eq!(base.network_id, 0);
+        assert_eq!(
+            base.reward_address().unwrap(),
+            "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn"
+        );
+
+        let enterprise: DecodedAddress =
+            "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8".parse().unwrap();
+        assert_eq!(enterprise.reward_address(), None);
+    }
+
+    #[test]
+    fn test_byron_addresses() {
+        let icarus: DecodedAddress =
+            "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi".parse().unwrap();
+        assert_eq!(icarus.kind, AddressKind::Byron);
+        assert!(icarus.is_mainnet());
+        assert_eq!(
+            icarus.to_string(),
+            "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi"
+        );
+
+        let testnet: DecodedAddress = "37btjrVyb4KDXBNC4haBVPCrro8AQPHwvCMp3RFhhSVWwfFmZ6wwzSK6JK1hY6wHNmtrpTf1kdbva8TCneM2YsiXT7mrzT21EacHnPpz5YyUdj64na"
+            .parse()
+            .unwrap();
+        assert_eq!(testnet.network_id, 0);
+
+        assert!("Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAj"
+            .parse::<DecodedAddress>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        // Mainnet header with testnet prefix
+        let mainnet = "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8";
+        let bytes = DecodedAddress::parse(mainnet).unwrap().bytes;
+        assert!(encode_bech32("addr_test", &bytes).parse::<DecodedAddress>().is_err());
+
+        // Reward address with payment prefix
+        let stake = "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw";
+        let bytes = DecodedAddress::parse(stake).unwrap().bytes;
+        assert!(encode_bech32("addr", &bytes).parse::<DecodedAddress>().is_err());
+
+        assert!("pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy"
+            .parse::<DecodedAddress>()
+            .is_err());
+    }
+}
@@ -51,7 +51,7 @@ impl BlockFrostApi {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Account {
     /// Bech32 stake address.
-    pub stake_address: String,
+    pub stake_address: StakeAddress,
     /// Registration state of an account.
     pub active: bool,
     /// Epoch of the most recent action - registration or deregistration.
@@ -69,7 +69,7 @@ pub struct Account {
     /// Sum of available rewards that haven't been withdrawn yet for the account in the Lovelaces.
     pub withdrawable_amount: String,
     /// Bech32 pool ID that owns the account.
-    pub pool_id: Option<String>,
+    pub pool_id: Option<PoolId>,
 }
 
 /// Created by [`accounts_rewards`](BlockFrostApi::accounts_rewards) method.
@@ -80,7 +80,7 @@ pub struct AccountReward {
     /// Rewards for given epoch in Lovelaces.
     pub amount: String,
     /// Bech32 pool ID being delegated to.
-    pub pool_id: String,
+    pub pool_id: PoolId,
 }
 
 /// Created by [`accounts_history`](BlockFrostApi::accounts_history) method.
@@ -91,7 +91,7 @@ pub struct AccountHistory {
     /// Rewards for given epoch in Lovelaces.
     pub amount: String,
     /// Bech32 pool ID being delegated to.
-    pub pool_id: String,
+    pub pool_id: PoolId,
 }
 
 /// Created by [`accounts_delegations`](BlockFrostApi::accounts_delegations) method.
@@ -100,18 +100,18 @@ pub struct AccountDelegation {
     /// Epoch in which the delegation becomes active.
     pub active_epoch: Integer,
     /// Hash of the transaction containing the delegation.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Rewards for given epoch in Lovelaces.
     pub amount: String,
     /// Bech32 ID of pool being delegated to.
-    pub pool_id: String,
+    pub pool_id: PoolId,
 }
 
 /// Created by [`accounts_registrations`](BlockFrostApi::accounts_registrations) method.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AccountRegistration {
     /// Hash of the transaction containing the (de)registration certificate.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Action in the certificate.
     pub action: ActionType, // "registered" | "deregistered"
 }
@@ -120,7 +120,7 @@ pub struct AccountRegistration {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AccountWithdrawal {
     /// Hash of the transaction containing the withdrawal.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Withdrawal amount in Lovelaces.
     pub amount: String,
 }
@@ -129,7 +129,7 @@ pub struct AccountWithdrawal {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AccountMir {
     /// Hash of the transaction containing the MIR.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// MIR amount in Lovelaces.
     pub amount: String,
 }
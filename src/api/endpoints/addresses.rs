@@ -17,6 +17,10 @@ impl BlockFrostApi {
         addresses_utxos(address: &str) -> Vec<AddressUtxo> => "/addresses/{address}/utxos";
             ("https://docs.blockfrost.io/#tag/Cardano-Addresses/paths/~1addresses~1{address}~1utxos/get"),
 
+        /// UTXOs of the address containing a specific asset.
+        addresses_utxos_asset(address: &str, asset: &str) -> Vec<AddressUtxo> => "/addresses/{address}/utxos/{asset}";
+            ("https://docs.blockfrost.io/#tag/Cardano-Addresses/paths/~1addresses~1{address}~1utxos~1{asset}/get"),
+
         /// Transactions on the address.
         addresses_transactions(address: &str) -> Vec<AddressTransaction> => "/addresses/{address}/transactions";
             ("https://docs.blockfrost.io/#tag/Cardano-Addresses/paths/~1addresses~1{address}~1transactions/get"),
@@ -31,7 +35,7 @@ pub struct Address {
     /// Sum of all owned assets.
     pub amount: Vec<Amount>,
     /// Stake address that controls the key.
-    pub stake_address: Option<String>,
+    pub stake_address: Option<StakeAddress>,
     /// Address era.
     #[serde(rename = "type")]
     pub type_: AdressType, // "byron" | "shelley"
@@ -50,19 +54,20 @@ pub struct AddressTotal {
     pub tx_count: Integer,
 }
 
-/// Created by [`addresses_utxos`](BlockFrostApi::addresses_utxos) method.
+/// Created by [`addresses_utxos`](BlockFrostApi::addresses_utxos) and
+/// [`addresses_utxos_asset`](BlockFrostApi::addresses_utxos_asset) methods.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AddressUtxo {
     /// Bech32 encoded addresses - useful when querying by payment_cred
     pub address: String,
     /// Transaction hash of the UTXO.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// UTXO index in the transaction.
     pub output_index: u32,
     /// Sum of assets for this UTXO.
     pub amount: Vec<Amount>,
     /// Block hash of the UTXO.
-    pub block: String,
+    pub block: BlockHash,
     /// The hash of the transaction output datum
     pub data_hash: Option<String>,
     /// CBOR encoded inline datum
@@ -75,7 +80,7 @@ pub struct AddressUtxo {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AddressTransaction {
     /// Hash of the transaction.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Transaction index within the block.
     pub tx_index: Integer,
     /// Block height.
@@ -144,6 +149,7 @@ mod tests {
     test_example! { test_address_utxo, Vec<AddressUtxo>, r#"
     [
       {
+        "address": "addr1qxqs59lphg8g6qndelq8xwqn60ag3aeyfcp33c2kdp46a09re5df3pzwwmyq946axfcejy5n4x0y99wqpgtp2gd0k09qsgy6pz",
         "tx_hash": "39a7a284c2a0948189dc45dec670211cd4d72f7b66c5726c08d9b3df11e44d58",
         "output_index": 0,
         "amount": [
@@ -155,6 +161,7 @@ mod tests {
         "block": "7eb8e27d18686c7db9a18f8bbcfe34e3fed6e047afaa2d969904d15e934847e6"
       },
       {
+        "address": "addr1qxqs59lphg8g6qndelq8xwqn60ag3aeyfcp33c2kdp46a09re5df3pzwwmyq946axfcejy5n4x0y99wqpgtp2gd0k09qsgy6pz",
         "tx_hash": "4c4e67bafa15e742c13c592b65c8f74c769cd7d9af04c848099672d1ba391b49",
         "output_index": 0,
         "amount": [
@@ -166,6 +173,7 @@ mod tests {
         "block": "953f1b80eb7c11a7ffcd67cbd4fde66e824a451aca5a4065725e5174b81685b7"
       },
       {
+        "address": "addr1qxqs59lphg8g6qndelq8xwqn60ag3aeyfcp33c2kdp46a09re5df3pzwwmyq946axfcejy5n4x0y99wqpgtp2gd0k09qsgy6pz",
         "tx_hash": "768c63e27a1c816a83dc7b07e78af673b2400de8849ea7e7b734ae1333d100d2",
         "output_index": 1,
         "amount": [
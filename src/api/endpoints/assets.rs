@@ -52,15 +52,15 @@ pub struct AssetDetails {
     /// Hex-encoded asset full name.
     pub asset: String,
     /// Policy ID of the asset.
-    pub policy_id: String,
+    pub policy_id: PolicyId,
     /// Hex-encoded asset name of the asset.
     pub asset_name: Option<String>,
     /// CIP14 based user-facing fingerprint.
-    pub fingerprint: String,
+    pub fingerprint: AssetFingerprint,
     /// Current asset quantity.
     pub quantity: String,
     /// ID of the initial minting transaction.
-    pub initial_mint_tx_hash: String,
+    pub initial_mint_tx_hash: TxHash,
     /// Count of mint and burn transactions.
     pub mint_or_burn_count: Integer,
     /// On-chain metadata stored in the minting transaction under label 721, community discussion
@@ -89,7 +89,7 @@ pub struct AssetMetadata {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AssetHistory {
     /// Hash of the transaction containing the asset action.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Action executed upon the asset policy.
     pub action: AssetHistoryActionType, // "minted" | "burned"
     /// Asset amount of the specific action.
@@ -100,7 +100,7 @@ pub struct AssetHistory {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AssetTransaction {
     /// Hash of the transaction.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Transaction index within the block.
     pub tx_index: Integer,
     /// Block height.
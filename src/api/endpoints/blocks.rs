@@ -59,7 +59,7 @@ pub struct Block {
     /// Block number.
     pub height: Option<Integer>,
     /// Hash of the block.
-    pub hash: String,
+    pub hash: BlockHash,
     /// Slot number.
     pub slot: Option<Integer>,
     /// Epoch number.
@@ -79,9 +79,9 @@ pub struct Block {
     /// VRF key of the block (exactly 65 characters).
     pub block_vrf: Option<String>,
     /// Hash of the previous block.
-    pub previous_block: Option<String>,
+    pub previous_block: Option<BlockHash>,
     /// Hash of the next block.
-    pub next_block: Option<String>,
+    pub next_block: Option<BlockHash>,
     /// Number of block confirmations.
     pub confirmations: Integer,
 }
@@ -91,12 +91,14 @@ pub struct AffectedAddress {
     /// Bech32 encoded addresses.
     pub address: String,
     /// Sum of all transaction.
-    pub transactions: Vec<TxHash>,
+    pub transactions: Vec<AffectedAddressTransaction>,
 }
+
+/// Inner member of [`AffectedAddress`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct TxHash {
+pub struct AffectedAddressTransaction {
     /// Transaction hash of the UTXO.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
 }
 
 #[cfg(test)]
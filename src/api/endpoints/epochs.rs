@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::*;
@@ -73,447 +75,201 @@ pub struct Epoch {
 }
 
 /// Created by [`epochs_latest_parameters`](BlockFrostApi::epochs_latest_parameters) method.
+///
+/// Parameters introduced by later eras are `None` for the epochs before them, e.g. the Plutus
+/// prices before Alonzo or the governance parameters before Conway.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EpochParameters {
+    /// Epoch number.
     pub epoch: i64,
-    #[serde(rename = "min_fee_a")]
+    /// The linear factor for the minimum fee calculation for given epoch.
     pub min_fee_a: i64,
-    #[serde(rename = "min_fee_b")]
+    /// The constant factor for the minimum fee calculation.
     pub min_fee_b: i64,
-    #[serde(rename = "max_block_size")]
+    /// Maximum block body size in Bytes.
     pub max_block_size: i64,
-    #[serde(rename = "max_tx_size")]
+    /// Maximum transaction size.
     pub max_tx_size: i64,
-    #[serde(rename = "max_block_header_size")]
+    /// Maximum block header size.
     pub max_block_header_size: i64,
-    #[serde(rename = "key_deposit")]
+    /// The amount of a key registration deposit in Lovelaces.
     pub key_deposit: String,
-    #[serde(rename = "pool_deposit")]
+    /// The amount of a pool registration deposit in Lovelaces.
     pub pool_deposit: String,
-    #[serde(rename = "e_max")]
+    /// Epoch bound on pool retirement.
     pub e_max: i64,
-    #[serde(rename = "n_opt")]
+    /// Desired number of pools.
     pub n_opt: i64,
+    /// Pool pledge influence.
     pub a0: f64,
+    /// Monetary expansion.
     pub rho: f64,
+    /// Treasury expansion.
     pub tau: f64,
-    #[serde(rename = "decentralisation_param")]
+    /// Percentage of blocks produced by federated nodes.
     pub decentralisation_param: f64,
-    #[serde(rename = "extra_entropy")]
+    /// Seed for extra entropy.
     pub extra_entropy: serde_json::Value,
-    #[serde(rename = "protocol_major_ver")]
+    /// Accepted protocol major version.
     pub protocol_major_ver: i64,
-    #[serde(rename = "protocol_minor_ver")]
+    /// Accepted protocol minor version.
     pub protocol_minor_ver: i64,
-    #[serde(rename = "min_utxo")]
+    /// Minimum UTXO value, before Alonzo.
     pub min_utxo: String,
-    #[serde(rename = "min_pool_cost")]
+    /// Minimum stake cost forced on the pool.
     pub min_pool_cost: String,
+    /// Epoch number only used once.
     pub nonce: String,
-    #[serde(rename = "cost_models")]
-    pub cost_models: CostModels,
-    #[serde(rename = "price_mem")]
-    pub price_mem: f64,
-    #[serde(rename = "price_step")]
-    pub price_step: f64,
-    #[serde(rename = "max_tx_ex_mem")]
-    pub max_tx_ex_mem: String,
-    #[serde(rename = "max_tx_ex_steps")]
-    pub max_tx_ex_steps: String,
-    #[serde(rename = "max_block_ex_mem")]
-    pub max_block_ex_mem: String,
-    #[serde(rename = "max_block_ex_steps")]
-    pub max_block_ex_steps: String,
-    #[serde(rename = "max_val_size")]
-    pub max_val_size: String,
-    #[serde(rename = "collateral_percent")]
-    pub collateral_percent: i64,
-    #[serde(rename = "max_collateral_inputs")]
-    pub max_collateral_inputs: i64,
-    #[serde(rename = "coins_per_utxo_size")]
-    pub coins_per_utxo_size: String,
-    #[serde(rename = "coins_per_utxo_word")]
-    pub coins_per_utxo_word: String,
+    /// Cost models parameters for Plutus Core scripts, keyed by parameter name.
+    pub cost_models: Option<CostModels>,
+    /// Cost models parameters for Plutus Core scripts, in the ledger order.
+    pub cost_models_raw: Option<CostModels>,
+    /// The per word cost of script memory usage.
+    pub price_mem: Option<f64>,
+    /// The cost of script execution step usage.
+    pub price_step: Option<f64>,
+    /// The maximum number of execution memory allowed to be used in a single transaction.
+    pub max_tx_ex_mem: Option<String>,
+    /// The maximum number of execution steps allowed to be used in a single transaction.
+    pub max_tx_ex_steps: Option<String>,
+    /// The maximum number of execution memory allowed to be used in a single block.
+    pub max_block_ex_mem: Option<String>,
+    /// The maximum number of execution steps allowed to be used in a single block.
+    pub max_block_ex_steps: Option<String>,
+    /// The maximum Val size.
+    pub max_val_size: Option<String>,
+    /// The percentage of the transactions fee which must be provided as collateral when including
+    /// non-native scripts.
+    pub collateral_percent: Option<i64>,
+    /// The maximum number of collateral inputs allowed in a transaction.
+    pub max_collateral_inputs: Option<i64>,
+    /// Cost per UTXO byte, since Babbage.
+    pub coins_per_utxo_size: Option<String>,
+    /// Cost per UTXO word, in Alonzo.
+    pub coins_per_utxo_word: Option<String>,
+    /// Pool voting threshold for motion of no-confidence.
+    pub pvt_motion_no_confidence: Option<f64>,
+    /// Pool voting threshold for new committee/threshold (normal state).
+    pub pvt_committee_normal: Option<f64>,
+    /// Pool voting threshold for new committee/threshold (state of no-confidence).
+    pub pvt_committee_no_confidence: Option<f64>,
+    /// Pool voting threshold for hard-fork initiation.
+    pub pvt_hard_fork_initiation: Option<f64>,
+    /// Pool voting threshold for protocol parameter changes, security group.
+    pub pvt_p_p_security_group: Option<f64>,
+    /// Same as `pvt_p_p_security_group`, under the name older API versions used.
+    pub pvtpp_security_group: Option<f64>,
+    /// DRep voting threshold for motion of no-confidence.
+    pub dvt_motion_no_confidence: Option<f64>,
+    /// DRep voting threshold for new committee/threshold (normal state).
+    pub dvt_committee_normal: Option<f64>,
+    /// DRep voting threshold for new committee/threshold (state of no-confidence).
+    pub dvt_committee_no_confidence: Option<f64>,
+    /// DRep voting threshold for update to the constitution.
+    pub dvt_update_to_constitution: Option<f64>,
+    /// DRep voting threshold for hard-fork initiation.
+    pub dvt_hard_fork_initiation: Option<f64>,
+    /// DRep voting threshold for protocol parameter changes, network group.
+    pub dvt_p_p_network_group: Option<f64>,
+    /// DRep voting threshold for protocol parameter changes, economic group.
+    pub dvt_p_p_economic_group: Option<f64>,
+    /// DRep voting threshold for protocol parameter changes, technical group.
+    pub dvt_p_p_technical_group: Option<f64>,
+    /// DRep voting threshold for protocol parameter changes, governance group.
+    pub dvt_p_p_gov_group: Option<f64>,
+    /// DRep voting threshold for treasury withdrawal.
+    pub dvt_treasury_withdrawal: Option<f64>,
+    /// Minimal constitutional committee size.
+    pub committee_min_size: Option<String>,
+    /// Constitutional committee term limit, in epochs.
+    pub committee_max_term_length: Option<String>,
+    /// Governance action expiration, in epochs.
+    pub gov_action_lifetime: Option<String>,
+    /// Governance action deposit in Lovelaces.
+    pub gov_action_deposit: Option<String>,
+    /// DRep deposit amount in Lovelaces.
+    pub drep_deposit: Option<String>,
+    /// DRep activity period, in epochs.
+    pub drep_activity: Option<String>,
+    /// Cost per byte of reference scripts, since the Conway era.
+    pub min_fee_ref_script_cost_per_byte: Option<f64>,
+}
+
+/// Inner type for [`EpochParameters`].
+///
+/// Cost models keyed by language name, e.g. `"PlutusV1"`, unknown languages are kept.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct CostModels(pub BTreeMap<String, CostModel>);
+
+impl CostModels {
+    /// The cost model of a language, if it is enabled in the epoch.
+    pub fn get(&self, language: PlutusLanguage) -> Option<&CostModel> {
+        self.0.get(language.name())
+    }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CostModels {
-    #[serde(rename = "PlutusV1")]
-    pub plutus_v1: PlutusV1,
-    #[serde(rename = "PlutusV2")]
-    pub plutus_v2: PlutusV2,
+/// Inner type for [`CostModels`].
+///
+/// Parameters of a cost model, named in `cost_models` or in the ledger order in
+/// `cost_models_raw`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CostModel {
+    Named(BTreeMap<String, i64>),
+    Positional(Vec<i64>),
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PlutusV2 {
-    #[serde(rename = "addInteger-cpu-arguments-intercept")]
-    pub add_integer_cpu_arguments_intercept: i64,
-    #[serde(rename = "addInteger-cpu-arguments-slope")]
-    pub add_integer_cpu_arguments_slope: i64,
-    #[serde(rename = "addInteger-memory-arguments-intercept")]
-    pub add_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "addInteger-memory-arguments-slope")]
-    pub add_integer_memory_arguments_slope: i64,
-    #[serde(rename = "appendByteString-cpu-arguments-intercept")]
-    pub append_byte_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "appendByteString-cpu-arguments-slope")]
-    pub append_byte_string_cpu_arguments_slope: i64,
-    #[serde(rename = "appendByteString-memory-arguments-intercept")]
-    pub append_byte_string_memory_arguments_intercept: i64,
-    #[serde(rename = "appendByteString-memory-arguments-slope")]
-    pub append_byte_string_memory_arguments_slope: i64,
-    #[serde(rename = "appendString-cpu-arguments-intercept")]
-    pub append_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "appendString-cpu-arguments-slope")]
-    pub append_string_cpu_arguments_slope: i64,
-    #[serde(rename = "appendString-memory-arguments-intercept")]
-    pub append_string_memory_arguments_intercept: i64,
-    #[serde(rename = "appendString-memory-arguments-slope")]
-    pub append_string_memory_arguments_slope: i64,
-    #[serde(rename = "bData-cpu-arguments")]
-    pub b_data_cpu_arguments: i64,
-    #[serde(rename = "bData-memory-arguments")]
-    pub b_data_memory_arguments: i64,
-    #[serde(rename = "blake2b_256-cpu-arguments-intercept")]
-    pub blake2b_256_cpu_arguments_intercept: i64,
-    #[serde(rename = "blake2b_256-cpu-arguments-slope")]
-    pub blake2b_256_cpu_arguments_slope: i64,
-    #[serde(rename = "blake2b_256-memory-arguments")]
-    pub blake2b_256_memory_arguments: i64,
-    #[serde(rename = "cekApplyCost-exBudgetCPU")]
-    pub cek_apply_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekApplyCost-exBudgetMemory")]
-    pub cek_apply_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekBuiltinCost-exBudgetCPU")]
-    pub cek_builtin_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekBuiltinCost-exBudgetMemory")]
-    pub cek_builtin_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekConstCost-exBudgetCPU")]
-    pub cek_const_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekConstCost-exBudgetMemory")]
-    pub cek_const_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekDelayCost-exBudgetCPU")]
-    pub cek_delay_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekDelayCost-exBudgetMemory")]
-    pub cek_delay_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekForceCost-exBudgetCPU")]
-    pub cek_force_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekForceCost-exBudgetMemory")]
-    pub cek_force_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekLamCost-exBudgetCPU")]
-    pub cek_lam_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekLamCost-exBudgetMemory")]
-    pub cek_lam_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekStartupCost-exBudgetCPU")]
-    pub cek_startup_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekStartupCost-exBudgetMemory")]
-    pub cek_startup_cost_ex_budget_memory: i64,
-    #[serde(rename = "cekVarCost-exBudgetCPU")]
-    pub cek_var_cost_ex_budget_cpu: i64,
-    #[serde(rename = "cekVarCost-exBudgetMemory")]
-    pub cek_var_cost_ex_budget_memory: i64,
-    #[serde(rename = "chooseData-cpu-arguments")]
-    pub choose_data_cpu_arguments: i64,
-    #[serde(rename = "chooseData-memory-arguments")]
-    pub choose_data_memory_arguments: i64,
-    #[serde(rename = "chooseList-cpu-arguments")]
-    pub choose_list_cpu_arguments: i64,
-    #[serde(rename = "chooseList-memory-arguments")]
-    pub choose_list_memory_arguments: i64,
-    #[serde(rename = "chooseUnit-cpu-arguments")]
-    pub choose_unit_cpu_arguments: i64,
-    #[serde(rename = "chooseUnit-memory-arguments")]
-    pub choose_unit_memory_arguments: i64,
-    #[serde(rename = "consByteString-cpu-arguments-intercept")]
-    pub cons_byte_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "consByteString-cpu-arguments-slope")]
-    pub cons_byte_string_cpu_arguments_slope: i64,
-    #[serde(rename = "consByteString-memory-arguments-intercept")]
-    pub cons_byte_string_memory_arguments_intercept: i64,
-    #[serde(rename = "consByteString-memory-arguments-slope")]
-    pub cons_byte_string_memory_arguments_slope: i64,
-    #[serde(rename = "constrData-cpu-arguments")]
-    pub constr_data_cpu_arguments: i64,
-    #[serde(rename = "constrData-memory-arguments")]
-    pub constr_data_memory_arguments: i64,
-    #[serde(rename = "decodeUtf8-cpu-arguments-intercept")]
-    pub decode_utf8_cpu_arguments_intercept: i64,
-    #[serde(rename = "decodeUtf8-cpu-arguments-slope")]
-    pub decode_utf8_cpu_arguments_slope: i64,
-    #[serde(rename = "decodeUtf8-memory-arguments-intercept")]
-    pub decode_utf8_memory_arguments_intercept: i64,
-    #[serde(rename = "decodeUtf8-memory-arguments-slope")]
-    pub decode_utf8_memory_arguments_slope: i64,
-    #[serde(rename = "divideInteger-cpu-arguments-constant")]
-    pub divide_integer_cpu_arguments_constant: i64,
-    #[serde(rename = "divideInteger-cpu-arguments-model-arguments-intercept")]
-    pub divide_integer_cpu_arguments_model_arguments_intercept: i64,
-    #[serde(rename = "divideInteger-cpu-arguments-model-arguments-slope")]
-    pub divide_integer_cpu_arguments_model_arguments_slope: i64,
-    #[serde(rename = "divideInteger-memory-arguments-intercept")]
-    pub divide_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "divideInteger-memory-arguments-minimum")]
-    pub divide_integer_memory_arguments_minimum: i64,
-    #[serde(rename = "divideInteger-memory-arguments-slope")]
-    pub divide_integer_memory_arguments_slope: i64,
-    #[serde(rename = "encodeUtf8-cpu-arguments-intercept")]
-    pub encode_utf8_cpu_arguments_intercept: i64,
-    #[serde(rename = "encodeUtf8-cpu-arguments-slope")]
-    pub encode_utf8_cpu_arguments_slope: i64,
-    #[serde(rename = "encodeUtf8-memory-arguments-intercept")]
-    pub encode_utf8_memory_arguments_intercept: i64,
-    #[serde(rename = "encodeUtf8-memory-arguments-slope")]
-    pub encode_utf8_memory_arguments_slope: i64,
-    #[serde(rename = "equalsByteString-cpu-arguments-constant")]
-    pub equals_byte_string_cpu_arguments_constant: i64,
-    #[serde(rename = "equalsByteString-cpu-arguments-intercept")]
-    pub equals_byte_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "equalsByteString-cpu-arguments-slope")]
-    pub equals_byte_string_cpu_arguments_slope: i64,
-    #[serde(rename = "equalsByteString-memory-arguments")]
-    pub equals_byte_string_memory_arguments: i64,
-    #[serde(rename = "equalsData-cpu-arguments-intercept")]
-    pub equals_data_cpu_arguments_intercept: i64,
-    #[serde(rename = "equalsData-cpu-arguments-slope")]
-    pub equals_data_cpu_arguments_slope: i64,
-    #[serde(rename = "equalsData-memory-arguments")]
-    pub equals_data_memory_arguments: i64,
-    #[serde(rename = "equalsInteger-cpu-arguments-intercept")]
-    pub equals_integer_cpu_arguments_intercept: i64,
-    #[serde(rename = "equalsInteger-cpu-arguments-slope")]
-    pub equals_integer_cpu_arguments_slope: i64,
-    #[serde(rename = "equalsInteger-memory-arguments")]
-    pub equals_integer_memory_arguments: i64,
-    #[serde(rename = "equalsString-cpu-arguments-constant")]
-    pub equals_string_cpu_arguments_constant: i64,
-    #[serde(rename = "equalsString-cpu-arguments-intercept")]
-    pub equals_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "equalsString-cpu-arguments-slope")]
-    pub equals_string_cpu_arguments_slope: i64,
-    #[serde(rename = "equalsString-memory-arguments")]
-    pub equals_string_memory_arguments: i64,
-    #[serde(rename = "fstPair-cpu-arguments")]
-    pub fst_pair_cpu_arguments: i64,
-    #[serde(rename = "fstPair-memory-arguments")]
-    pub fst_pair_memory_arguments: i64,
-    #[serde(rename = "headList-cpu-arguments")]
-    pub head_list_cpu_arguments: i64,
-    #[serde(rename = "headList-memory-arguments")]
-    pub head_list_memory_arguments: i64,
-    #[serde(rename = "iData-cpu-arguments")]
-    pub i_data_cpu_arguments: i64,
-    #[serde(rename = "iData-memory-arguments")]
-    pub i_data_memory_arguments: i64,
-    #[serde(rename = "ifThenElse-cpu-arguments")]
-    pub if_then_else_cpu_arguments: i64,
-    #[serde(rename = "ifThenElse-memory-arguments")]
-    pub if_then_else_memory_arguments: i64,
-    #[serde(rename = "indexByteString-cpu-arguments")]
-    pub index_byte_string_cpu_arguments: i64,
-    #[serde(rename = "indexByteString-memory-arguments")]
-    pub index_byte_string_memory_arguments: i64,
-    #[serde(rename = "lengthOfByteString-cpu-arguments")]
-    pub length_of_byte_string_cpu_arguments: i64,
-    #[serde(rename = "lengthOfByteString-memory-arguments")]
-    pub length_of_byte_string_memory_arguments: i64,
-    #[serde(rename = "lessThanByteString-cpu-arguments-intercept")]
-    pub less_than_byte_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "lessThanByteString-cpu-arguments-slope")]
-    pub less_than_byte_string_cpu_arguments_slope: i64,
-    #[serde(rename = "lessThanByteString-memory-arguments")]
-    pub less_than_byte_string_memory_arguments: i64,
-    #[serde(rename = "lessThanEqualsByteString-cpu-arguments-intercept")]
-    pub less_than_equals_byte_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "lessThanEqualsByteString-cpu-arguments-slope")]
-    pub less_than_equals_byte_string_cpu_arguments_slope: i64,
-    #[serde(rename = "lessThanEqualsByteString-memory-arguments")]
-    pub less_than_equals_byte_string_memory_arguments: i64,
-    #[serde(rename = "lessThanEqualsInteger-cpu-arguments-intercept")]
-    pub less_than_equals_integer_cpu_arguments_intercept: i64,
-    #[serde(rename = "lessThanEqualsInteger-cpu-arguments-slope")]
-    pub less_than_equals_integer_cpu_arguments_slope: i64,
-    #[serde(rename = "lessThanEqualsInteger-memory-arguments")]
-    pub less_than_equals_integer_memory_arguments: i64,
-    #[serde(rename = "lessThanInteger-cpu-arguments-intercept")]
-    pub less_than_integer_cpu_arguments_intercept: i64,
-    #[serde(rename = "lessThanInteger-cpu-arguments-slope")]
-    pub less_than_integer_cpu_arguments_slope: i64,
-    #[serde(rename = "lessThanInteger-memory-arguments")]
-    pub less_than_integer_memory_arguments: i64,
-    #[serde(rename = "listData-cpu-arguments")]
-    pub list_data_cpu_arguments: i64,
-    #[serde(rename = "listData-memory-arguments")]
-    pub list_data_memory_arguments: i64,
-    #[serde(rename = "mapData-cpu-arguments")]
-    pub map_data_cpu_arguments: i64,
-    #[serde(rename = "mapData-memory-arguments")]
-    pub map_data_memory_arguments: i64,
-    #[serde(rename = "mkCons-cpu-arguments")]
-    pub mk_cons_cpu_arguments: i64,
-    #[serde(rename = "mkCons-memory-arguments")]
-    pub mk_cons_memory_arguments: i64,
-    #[serde(rename = "mkNilData-cpu-arguments")]
-    pub mk_nil_data_cpu_arguments: i64,
-    #[serde(rename = "mkNilData-memory-arguments")]
-    pub mk_nil_data_memory_arguments: i64,
-    #[serde(rename = "mkNilPairData-cpu-arguments")]
-    pub mk_nil_pair_data_cpu_arguments: i64,
-    #[serde(rename = "mkNilPairData-memory-arguments")]
-    pub mk_nil_pair_data_memory_arguments: i64,
-    #[serde(rename = "mkPairData-cpu-arguments")]
-    pub mk_pair_data_cpu_arguments: i64,
-    #[serde(rename = "mkPairData-memory-arguments")]
-    pub mk_pair_data_memory_arguments: i64,
-    #[serde(rename = "modInteger-cpu-arguments-constant")]
-    pub mod_integer_cpu_arguments_constant: i64,
-    #[serde(rename = "modInteger-cpu-arguments-model-arguments-intercept")]
-    pub mod_integer_cpu_arguments_model_arguments_intercept: i64,
-    #[serde(rename = "modInteger-cpu-arguments-model-arguments-slope")]
-    pub mod_integer_cpu_arguments_model_arguments_slope: i64,
-    #[serde(rename = "modInteger-memory-arguments-intercept")]
-    pub mod_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "modInteger-memory-arguments-minimum")]
-    pub mod_integer_memory_arguments_minimum: i64,
-    #[serde(rename = "modInteger-memory-arguments-slope")]
-    pub mod_integer_memory_arguments_slope: i64,
-    #[serde(rename = "multiplyInteger-cpu-arguments-intercept")]
-    pub multiply_integer_cpu_arguments_intercept: i64,
-    #[serde(rename = "multiplyInteger-cpu-arguments-slope")]
-    pub multiply_integer_cpu_arguments_slope: i64,
-    #[serde(rename = "multiplyInteger-memory-arguments-intercept")]
-    pub multiply_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "multiplyInteger-memory-arguments-slope")]
-    pub multiply_integer_memory_arguments_slope: i64,
-    #[serde(rename = "nullList-cpu-arguments")]
-    pub null_list_cpu_arguments: i64,
-    #[serde(rename = "nullList-memory-arguments")]
-    pub null_list_memory_arguments: i64,
-    #[serde(rename = "quotientInteger-cpu-arguments-constant")]
-    pub quotient_integer_cpu_arguments_constant: i64,
-    #[serde(rename = "quotientInteger-cpu-arguments-model-arguments-intercept")]
-    pub quotient_integer_cpu_arguments_model_arguments_intercept: i64,
-    #[serde(rename = "quotientInteger-cpu-arguments-model-arguments-slope")]
-    pub quotient_integer_cpu_arguments_model_arguments_slope: i64,
-    #[serde(rename = "quotientInteger-memory-arguments-intercept")]
-    pub quotient_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "quotientInteger-memory-arguments-minimum")]
-    pub quotient_integer_memory_arguments_minimum: i64,
-    #[serde(rename = "quotientInteger-memory-arguments-slope")]
-    pub quotient_integer_memory_arguments_slope: i64,
-    #[serde(rename = "remainderInteger-cpu-arguments-constant")]
-    pub remainder_integer_cpu_arguments_constant: i64,
-    #[serde(rename = "remainderInteger-cpu-arguments-model-arguments-intercept")]
-    pub remainder_integer_cpu_arguments_model_arguments_intercept: i64,
-    #[serde(rename = "remainderInteger-cpu-arguments-model-arguments-slope")]
-    pub remainder_integer_cpu_arguments_model_arguments_slope: i64,
-    #[serde(rename = "remainderInteger-memory-arguments-intercept")]
-    pub remainder_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "remainderInteger-memory-arguments-minimum")]
-    pub remainder_integer_memory_arguments_minimum: i64,
-    #[serde(rename = "remainderInteger-memory-arguments-slope")]
-    pub remainder_integer_memory_arguments_slope: i64,
-    #[serde(rename = "serialiseData-cpu-arguments-intercept")]
-    pub serialise_data_cpu_arguments_intercept: i64,
-    #[serde(rename = "serialiseData-cpu-arguments-slope")]
-    pub serialise_data_cpu_arguments_slope: i64,
-    #[serde(rename = "serialiseData-memory-arguments-intercept")]
-    pub serialise_data_memory_arguments_intercept: i64,
-    #[serde(rename = "serialiseData-memory-arguments-slope")]
-    pub serialise_data_memory_arguments_slope: i64,
-    #[serde(rename = "sha2_256-cpu-arguments-intercept")]
-    pub sha2_256_cpu_arguments_intercept: i64,
-    #[serde(rename = "sha2_256-cpu-arguments-slope")]
-    pub sha2_256_cpu_arguments_slope: i64,
-    #[serde(rename = "sha2_256-memory-arguments")]
-    pub sha2_256_memory_arguments: i64,
-    #[serde(rename = "sha3_256-cpu-arguments-intercept")]
-    pub sha3_256_cpu_arguments_intercept: i64,
-    #[serde(rename = "sha3_256-cpu-arguments-slope")]
-    pub sha3_256_cpu_arguments_slope: i64,
-    #[serde(rename = "sha3_256-memory-arguments")]
-    pub sha3_256_memory_arguments: i64,
-    #[serde(rename = "sliceByteString-cpu-arguments-intercept")]
-    pub slice_byte_string_cpu_arguments_intercept: i64,
-    #[serde(rename = "sliceByteString-cpu-arguments-slope")]
-    pub slice_byte_string_cpu_arguments_slope: i64,
-    #[serde(rename = "sliceByteString-memory-arguments-intercept")]
-    pub slice_byte_string_memory_arguments_intercept: i64,
-    #[serde(rename = "sliceByteString-memory-arguments-slope")]
-    pub slice_byte_string_memory_arguments_slope: i64,
-    #[serde(rename = "sndPair-cpu-arguments")]
-    pub snd_pair_cpu_arguments: i64,
-    #[serde(rename = "sndPair-memory-arguments")]
-    pub snd_pair_memory_arguments: i64,
-    #[serde(rename = "subtractInteger-cpu-arguments-intercept")]
-    pub subtract_integer_cpu_arguments_intercept: i64,
-    #[serde(rename = "subtractInteger-cpu-arguments-slope")]
-    pub subtract_integer_cpu_arguments_slope: i64,
-    #[serde(rename = "subtractInteger-memory-arguments-intercept")]
-    pub subtract_integer_memory_arguments_intercept: i64,
-    #[serde(rename = "subtractInteger-memory-arguments-slope")]
-    pub subtract_integer_memory_arguments_slope: i64,
-    #[serde(rename = "tailList-cpu-arguments")]
-    pub tail_list_cpu_arguments: i64,
-    #[serde(rename = "tailList-memory-arguments")]
-    pub tail_list_memory_arguments: i64,
-    #[serde(rename = "trace-cpu-arguments")]
-    pub trace_cpu_arguments: i64,
-    #[serde(rename = "trace-memory-arguments")]
-    pub trace_memory_arguments: i64,
-    #[serde(rename = "unBData-cpu-arguments")]
-    pub un_bdata_cpu_arguments: i64,
-    #[serde(rename = "unBData-memory-arguments")]
-    pub un_bdata_memory_arguments: i64,
-    #[serde(rename = "unConstrData-cpu-arguments")]
-    pub un_constr_data_cpu_arguments: i64,
-    #[serde(rename = "unConstrData-memory-arguments")]
-    pub un_constr_data_memory_arguments: i64,
-    #[serde(rename = "unIData-cpu-arguments")]
-    pub un_idata_cpu_arguments: i64,
-    #[serde(rename = "unIData-memory-arguments")]
-    pub un_idata_memory_arguments: i64,
-    #[serde(rename = "unListData-cpu-arguments")]
-    pub un_list_data_cpu_arguments: i64,
-    #[serde(rename = "unListData-memory-arguments")]
-    pub un_list_data_memory_arguments: i64,
-    #[serde(rename = "unMapData-cpu-arguments")]
-    pub un_map_data_cpu_arguments: i64,
-    #[serde(rename = "unMapData-memory-arguments")]
-    pub un_map_data_memory_arguments: i64,
-    #[serde(rename = "verifyEcdsaSecp256k1Signature-cpu-arguments")]
-    pub verify_ecdsa_secp256k1_signature_cpu_arguments: i64,
-    #[serde(rename = "verifyEcdsaSecp256k1Signature-memory-arguments")]
-    pub verify_ecdsa_secp256k1_signature_memory_arguments: i64,
-    #[serde(rename = "verifyEd25519Signature-cpu-arguments-intercept")]
-    pub verify_ed25519_signature_cpu_arguments_intercept: i64,
-    #[serde(rename = "verifyEd25519Signature-cpu-arguments-slope")]
-    pub verify_ed25519_signature_cpu_arguments_slope: i64,
-    #[serde(rename = "verifyEd25519Signature-memory-arguments")]
-    pub verify_ed25519_signature_memory_arguments: i64,
-    #[serde(rename = "verifySchnorrSecp256k1Signature-cpu-arguments-intercept")]
-    pub verify_schnorr_secp256k1_signature_cpu_arguments_intercept: i64,
-    #[serde(rename = "verifySchnorrSecp256k1Signature-cpu-arguments-slope")]
-    pub verify_schnorr_secp256k1_signature_cpu_arguments_slope: i64,
-    #[serde(rename = "verifySchnorrSecp256k1Signature-memory-arguments")]
-    pub verify_schnorr_secp256k1_signature_memory_arguments: i64
-    
+impl CostModel {
+    /// Number of parameters.
+    pub fn len(&self) -> usize {
+        match self {
+            CostModel::Named(parameters) => parameters.len(),
+            CostModel::Positional(parameters) => parameters.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// A parameter by name, `None` for positional cost models.
+    pub fn get(&self, name: &str) -> Option<i64> {
+        match self {
+            CostModel::Named(parameters) => parameters.get(name).copied(),
+            CostModel::Positional(_) => None,
+        }
+    }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PlutusV1 {
-   pub who_cares : Option<String>
+/// Inner enum for [`CostModels`].
+///
+/// Plutus language version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PlutusLanguage {
+    PlutusV1,
+    PlutusV2,
+    PlutusV3,
+}
+
+impl PlutusLanguage {
+    /// Name of the language, as keyed in [`CostModels`].
+    pub fn name(self) -> &'static str {
+        match self {
+            PlutusLanguage::PlutusV1 => "PlutusV1",
+            PlutusLanguage::PlutusV2 => "PlutusV2",
+            PlutusLanguage::PlutusV3 => "PlutusV3",
+        }
+    }
 }
 
 /// Created by [`epochs_stakes`](BlockFrostApi::epochs_stakes) method.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AddressStakePool {
     /// Stake address.
-    pub stake_address: String,
+    pub stake_address: StakeAddress,
     /// Bech32 prefix of the pool delegated to.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Amount of active delegated stake in Lovelaces.
     pub amount: String,
 }
@@ -522,7 +278,7 @@ pub struct AddressStakePool {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AddressStake {
     /// Stake address.
-    pub stake_address: String,
+    pub stake_address: StakeAddress,
     /// Amount of active delegated stake in Lovelaces.
     pub amount: String,
 }
@@ -575,12 +331,140 @@ mod tests {
         "max_block_ex_mem": "110000000000",
         "max_block_ex_steps": "110000000000",
         "max_val_size": "5000",
-        "collateral_percent": 1.5,
+        "collateral_percent": 150,
         "max_collateral_inputs": 6,
-        "coins_per_utxo_word": "34482"
+        "coins_per_utxo_size": "4310",
+        "coins_per_utxo_word": "34482",
+        "cost_models": {
+            "PlutusV1": {
+                "addInteger-cpu-arguments-intercept": 197209,
+                "addInteger-cpu-arguments-slope": 0
+            },
+            "PlutusV2": {
+                "addInteger-cpu-arguments-intercept": 197209,
+                "addInteger-cpu-arguments-slope": 0
+            }
+        }
     }
     "# }
 
+    test_example! { test_epochs_parameters_shelley, EpochParameters, r#"
+    {
+        "epoch": 210,
+        "min_fee_a": 44,
+        "min_fee_b": 155381,
+        "max_block_size": 65536,
+        "max_tx_size": 16384,
+        "max_block_header_size": 1100,
+        "key_deposit": "2000000",
+        "pool_deposit": "500000000",
+        "e_max": 18,
+        "n_opt": 150,
+        "a0": 0.3,
+        "rho": 0.003,
+        "tau": 0.2,
+        "decentralisation_param": 1,
+        "extra_entropy": null,
+        "protocol_major_ver": 2,
+        "protocol_minor_ver": 0,
+        "min_utxo": "1000000",
+        "min_pool_cost": "340000000",
+        "nonce": "1a3be38bcbb7911969283716ad7aa550250226b76a61fc51cc9a9a35d9276d81",
+        "cost_models": null,
+        "cost_models_raw": null,
+        "price_mem": null,
+        "price_step": null,
+        "max_tx_ex_mem": null,
+        "max_tx_ex_steps": null,
+        "max_block_ex_mem": null,
+        "max_block_ex_steps": null,
+        "max_val_size": null,
+        "collateral_percent": null,
+        "max_collateral_inputs": null,
+        "coins_per_utxo_size": null,
+        "coins_per_utxo_word": null
+    }
+    "# }
+
+    test_example! { test_epochs_parameters_conway, EpochParameters, r#"
+    {
+        "epoch": 507,
+        "min_fee_a": 44,
+        "min_fee_b": 155381,
+        "max_block_size": 90112,
+        "max_tx_size": 16384,
+        "max_block_header_size": 1100,
+        "key_deposit": "2000000",
+        "pool_deposit": "500000000",
+        "e_max": 18,
+        "n_opt": 500,
+        "a0": 0.3,
+        "rho": 0.003,
+        "tau": 0.2,
+        "decentralisation_param": 0,
+        "extra_entropy": null,
+        "protocol_major_ver": 9,
+        "protocol_minor_ver": 0,
+        "min_utxo": "4310",
+        "min_pool_cost": "170000000",
+        "nonce": "1a3be38bcbb7911969283716ad7aa550250226b76a61fc51cc9a9a35d9276d81",
+        "cost_models_raw": {
+            "PlutusV1": [100788, 420, 1, 1],
+            "PlutusV2": [100788, 420, 1, 1, 1000],
+            "PlutusV3": [100788, 420, 1, 1, 1000, 173]
+        },
+        "price_mem": 0.0577,
+        "price_step": 0.0000721,
+        "max_tx_ex_mem": "14000000",
+        "max_tx_ex_steps": "10000000000",
+        "max_block_ex_mem": "62000000",
+        "max_block_ex_steps": "20000000000",
+        "max_val_size": "5000",
+        "collateral_percent": 150,
+        "max_collateral_inputs": 3,
+        "coins_per_utxo_size": "4310",
+        "coins_per_utxo_word": "4310",
+        "pvt_motion_no_confidence": 0.51,
+        "pvt_committee_normal": 0.51,
+        "pvt_committee_no_confidence": 0.51,
+        "pvt_hard_fork_initiation": 0.51,
+        "pvt_p_p_security_group": 0.51,
+        "pvtpp_security_group": 0.51,
+        "dvt_motion_no_confidence": 0.67,
+        "dvt_committee_normal": 0.67,
+        "dvt_committee_no_confidence": 0.6,
+        "dvt_update_to_constitution": 0.75,
+        "dvt_hard_fork_initiation": 0.6,
+        "dvt_p_p_network_group": 0.67,
+        "dvt_p_p_economic_group": 0.67,
+        "dvt_p_p_technical_group": 0.67,
+        "dvt_p_p_gov_group": 0.75,
+        "dvt_treasury_withdrawal": 0.67,
+        "committee_min_size": "7",
+        "committee_max_term_length": "146",
+        "gov_action_lifetime": "6",
+        "gov_action_deposit": "100000000000",
+        "drep_deposit": "500000000",
+        "drep_activity": "20",
+        "min_fee_ref_script_cost_per_byte": 15
+    }
+    "# }
+
+    #[test]
+    fn test_cost_models() {
+        let cost_models: CostModels = serde_json::from_str(
+            r#"{ "PlutusV1": { "addInteger-cpu-arguments-intercept": 197209 }, "PlutusV3": [1, 2] }"#,
+        )
+        .unwrap();
+
+        let v1 = cost_models.get(PlutusLanguage::PlutusV1).unwrap();
+        assert_eq!(v1.get("addInteger-cpu-arguments-intercept"), Some(197209));
+        assert_eq!(cost_models.get(PlutusLanguage::PlutusV2), None);
+        let v3 = cost_models.get(PlutusLanguage::PlutusV3).unwrap();
+        assert_eq!(v3, &CostModel::Positional(vec![1, 2]));
+        assert_eq!(v3.get("addInteger-cpu-arguments-intercept"), None);
+    }
+
     test_example! { test_epochs_next, Vec<Epoch>, r#"
     [
         {
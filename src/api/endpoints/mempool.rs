@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+impl BlockFrostApi {
+    endpoints! {
+        /// Content of a transaction in the mempool.
+        mempool_by_hash(hash: &str) -> MempoolTransaction => "/mempool/{hash}";
+            ("https://docs.blockfrost.io/#tag/Cardano-Mempool/paths/~1mempool~1{hash}/get"),
+    }
+
+    paged_endpoints! {
+        /// Transactions submitted through Blockfrost that are still in the mempool.
+        mempool() -> Vec<MempoolTxHash> => "/mempool";
+            ("https://docs.blockfrost.io/#tag/Cardano-Mempool/paths/~1mempool/get"),
+
+        /// Transactions in the mempool that spend from or send to an address.
+        mempool_by_address(address: &str) -> Vec<MempoolTxHash> => "/mempool/addresses/{address}";
+            ("https://docs.blockfrost.io/#tag/Cardano-Mempool/paths/~1mempool~1addresses~1{address}/get"),
+    }
+}
+
+/// Created by [`mempool`](BlockFrostApi::mempool) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MempoolTxHash {
+    /// Hash of the transaction.
+    pub tx_hash: TxHash,
+}
+
+/// Created by [`mempool_by_hash`](BlockFrostApi::mempool_by_hash) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MempoolTransaction {
+    pub tx: MempoolTx,
+    pub inputs: Vec<MempoolTxInput>,
+    pub outputs: Vec<MempoolTxOutput>,
+    pub redeemers: Option<Vec<MempoolTxRedeemer>>,
+}
+
+/// Inner member of [`MempoolTransaction`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MempoolTx {
+    /// Transaction hash.
+    pub hash: TxHash,
+    /// Amounts of the transaction.
+    pub output_amount: Vec<Amount>,
+    /// Fees of the transaction in Lovelaces.
+    pub fees: String,
+    /// Deposit within the transaction in Lovelaces.
+    pub deposit: String,
+    /// Size of the transaction in Bytes.
+    pub size: Integer,
+    /// Left (included) endpoint of the timelock validity intervals.
+    pub invalid_before: Option<String>,
+    /// Right (excluded) endpoint of the timelock validity intervals.
+    pub invalid_hereafter: Option<String>,
+    /// Count of UTXOs within the transaction.
+    pub utxo_count: Integer,
+    /// Count of the withdrawals within the transaction.
+    pub withdrawal_count: Integer,
+    /// Count of the MIR certificates within the transaction.
+    pub mir_cert_count: Integer,
+    /// Count of the delegations within the transaction.
+    pub delegation_count: Integer,
+    /// Count of the stake keys (de)registration and delegation certificates within the transaction.
+    pub stake_cert_count: Integer,
+    /// Count of the stake pool registration and update certificates within the transaction.
+    pub pool_update_count: Integer,
+    /// Count of the stake pool retirement certificates within the transaction.
+    pub pool_retire_count: Integer,
+    /// Count of asset mints and burns within the transaction.
+    pub asset_mint_or_burn_count: Integer,
+    /// Count of redeemers within the transaction.
+    pub redeemer_count: Integer,
+    /// True if contract script passed validation.
+    pub valid_contract: bool,
+}
+
+/// Inner member of [`MempoolTransaction`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MempoolTxInput {
+    /// Input address, `None` if the spent output is not indexed yet.
+    pub address: Option<String>,
+    /// Hash of the UTXO transaction.
+    pub tx_hash: TxHash,
+    /// UTXO index in the transaction.
+    pub output_index: Integer,
+    /// Whether the input is a collateral consumed on script validation failure.
+    pub collateral: bool,
+    /// Whether the input is a reference input.
+    pub reference: Option<bool>,
+}
+
+/// Inner member of [`MempoolTransaction`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MempoolTxOutput {
+    /// Output address.
+    pub address: String,
+    pub amount: Vec<Amount>,
+    /// UTXO index in the transaction.
+    pub output_index: Integer,
+    /// The hash of the transaction output datum.
+    pub data_hash: Option<String>,
+    /// CBOR encoded inline datum.
+    pub inline_datum: Option<String>,
+    /// Whether the output is a collateral output.
+    pub collateral: bool,
+    /// The hash of the reference script of the output.
+    pub reference_script_hash: Option<String>,
+}
+
+/// Inner member of [`MempoolTransaction`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MempoolTxRedeemer {
+    /// Index of the redeemer within the transaction.
+    pub tx_index: Integer,
+    /// Validation purpose.
+    pub purpose: RedeemerPurpose,
+    /// The budget in Memory to run a script.
+    pub unit_mem: String,
+    /// The budget in CPU steps to run a script.
+    pub unit_steps: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_example! { test_mempool, Vec<MempoolTxHash>, r#"
+    [
+      {
+        "tx_hash": "abc1230000000000000000000000000000000000000000000000000000000000"
+      },
+      {
+        "tx_hash": "4567890000000000000000000000000000000000000000000000000000000000"
+      }
+    ]
+    "# }
+
+    test_example! { test_mempool_by_hash, MempoolTransaction, r#"
+    {
+      "tx": {
+        "hash": "1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477",
+        "output_amount": [
+          {
+            "unit": "lovelace",
+            "quantity": "42000000"
+          }
+        ],
+        "fees": "182485",
+        "deposit": "0",
+        "size": 433,
+        "invalid_before": null,
+        "invalid_hereafter": "13885913",
+        "utxo_count": 4,
+        "withdrawal_count": 0,
+        "mir_cert_count": 0,
+        "delegation_count": 0,
+        "stake_cert_count": 0,
+        "pool_update_count": 0,
+        "pool_retire_count": 0,
+        "asset_mint_or_burn_count": 0,
+        "redeemer_count": 0,
+        "valid_contract": true
+      },
+      "inputs": [
+        {
+          "address": "addr1q9ld26v2lv8wvrxxmvg90pn8n8n5k6tdst06q2s856rwmvnueldzuuqmnsye359fqrk8hwvenjnqultn7djtrlft7jnq7dy7wv",
+          "tx_hash": "1a0570af966fb355a7160e4f82d5a80b8681b7955f5d44bec0dce628516157f0",
+          "output_index": 0,
+          "collateral": false,
+          "reference": false
+        }
+      ],
+      "outputs": [
+        {
+          "address": "addr1q9ld26v2lv8wvrxxmvg90pn8n8n5k6tdst06q2s856rwmvnueldzuuqmnsye359fqrk8hwvenjnqultn7djtrlft7jnq7dy7wv",
+          "amount": [
+            {
+              "unit": "lovelace",
+              "quantity": "42000000"
+            }
+          ],
+          "output_index": 0,
+          "data_hash": null,
+          "inline_datum": null,
+          "collateral": false,
+          "reference_script_hash": null
+        }
+      ],
+      "redeemers": [
+        {
+          "tx_index": 0,
+          "purpose": "spend",
+          "unit_mem": "1700",
+          "unit_steps": "476468"
+        }
+      ]
+    }
+    "# }
+}
@@ -33,7 +33,7 @@ pub struct MetadataTxsLabel {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MetadataTxsLabelJson {
     /// Transaction hash that contains the specific metadata.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Content of the JSON metadata.
     pub json_metadata: Option<JsonValue>,
 }
@@ -42,7 +42,7 @@ pub struct MetadataTxsLabelJson {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MetadataTxsLabelCbor {
     /// Transaction hash that contains the specific metadata.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Content of the CBOR metadata.
     pub cbor_metadata: Option<String>,
 }
@@ -5,6 +5,7 @@ mod blocks;
 mod epochs;
 mod health;
 mod ledger;
+mod mempool;
 mod metadata;
 mod metrics;
 mod network;
@@ -20,6 +21,7 @@ pub use blocks::*;
 pub use epochs::*;
 pub use health::*;
 pub use ledger::*;
+pub use mempool::*;
 pub use metadata::*;
 pub use metrics::*;
 pub use network::*;
@@ -51,7 +51,7 @@ pub struct NutlinkAddressTicker {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NutlinkAddressTickerDetailed {
     /// Hash of the transaction.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Block height of the record.
     pub block_height: Integer,
     /// Transaction index within the block.
@@ -66,7 +66,7 @@ pub struct NutlinkTicker {
     /// Address of a metadata oracle.
     pub address: String,
     /// Hash of the transaction.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Block height of the record.
     pub block_height: Integer,
     /// Transaction index within the block.
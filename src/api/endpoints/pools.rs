@@ -51,7 +51,7 @@ impl BlockFrostApi {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RetiredPool {
     /// Bech32 encoded pool ID.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Retirement epoch number.
     pub epoch: Integer,
 }
@@ -60,7 +60,7 @@ pub struct RetiredPool {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RetiringPool {
     /// Bech32 encoded pool ID.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Retirement epoch number.
     pub epoch: Integer,
 }
@@ -69,7 +69,7 @@ pub struct RetiringPool {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Pool {
     /// Bech32 pool ID.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Hexadecimal pool ID.
     pub hex: String,
     /// VRF key hash.
@@ -120,7 +120,7 @@ pub struct PoolHistory {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PoolMetadata {
     /// Bech32 pool ID.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Hexadecimal pool ID.
     pub hex: String,
     /// URL to the stake pool metadata.
@@ -165,7 +165,7 @@ pub struct PoolDelegator {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PoolUpdate {
     /// Transaction ID.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// Certificate within the transaction.
     pub cert_index: Integer,
     /// Action in the certificate.
@@ -12,6 +12,22 @@ impl BlockFrostApi {
         scripts_by_id(script_hash: &str) -> Script => "/scripts/{script_hash}";
             ("https://docs.blockfrost.io/#tag/Cardano-Scripts/paths/~1scripts~1{script_hash}/get"),
 
+        /// JSON of a timelock script, `null` for Plutus scripts.
+        scripts_json(script_hash: &str) -> ScriptJson => "/scripts/{script_hash}/json";
+            ("https://docs.blockfrost.io/#tag/Cardano-Scripts/paths/~1scripts~1{script_hash}~1json/get"),
+
+        /// CBOR of a Plutus script, `null` for timelock scripts.
+        scripts_cbor(script_hash: &str) -> ScriptCbor => "/scripts/{script_hash}/cbor";
+            ("https://docs.blockfrost.io/#tag/Cardano-Scripts/paths/~1scripts~1{script_hash}~1cbor/get"),
+
+        /// Query JSON value of a datum by its hash.
+        scripts_datum(datum_hash: &str) -> ScriptDatum => "/scripts/datum/{datum_hash}";
+            ("https://docs.blockfrost.io/#tag/Cardano-Scripts/paths/~1scripts~1datum~1{datum_hash}/get"),
+
+        /// Query CBOR serialised datum by its hash.
+        scripts_datum_cbor(datum_hash: &str) -> ScriptDatumCbor => "/scripts/datum/{datum_hash}/cbor";
+            ("https://docs.blockfrost.io/#tag/Cardano-Scripts/paths/~1scripts~1datum~1{datum_hash}~1cbor/get"),
+
         /// List of redeemers of a specific script.
         scripts_redeemers(script_hash: &str) -> Vec<ScriptRedeemer> => "/scripts/{script_hash}/redeemers";
             ("https://docs.blockfrost.io/#tag/Cardano-Scripts/paths/~1scripts~1{script_hash}~1redeemers/get"),
@@ -36,11 +52,39 @@ pub struct Script {
     pub serialised_size: Option<Integer>,
 }
 
+/// Created by [`scripts_json`](BlockFrostApi::scripts_json) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScriptJson {
+    /// JSON contents of the timelock script, `None` for Plutus scripts.
+    pub json: Option<JsonValue>,
+}
+
+/// Created by [`scripts_cbor`](BlockFrostApi::scripts_cbor) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScriptCbor {
+    /// Hex-encoded CBOR contents of the Plutus script, `None` for timelock scripts.
+    pub cbor: Option<String>,
+}
+
+/// Created by [`scripts_datum`](BlockFrostApi::scripts_datum) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScriptDatum {
+    /// JSON content of the datum, in the detailed schema of `cardano-cli`.
+    pub json_value: JsonValue,
+}
+
+/// Created by [`scripts_datum_cbor`](BlockFrostApi::scripts_datum_cbor) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScriptDatumCbor {
+    /// Hex-encoded CBOR of the datum.
+    pub cbor: String,
+}
+
 /// Created by [`scripts_redeemers`](BlockFrostApi::scripts_redeemers) method.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ScriptRedeemer {
     /// Hash of the transaction.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// The index of the redeemer pointer in the transaction.
     pub tx_index: Integer,
     /// Validation purpose.
@@ -60,7 +104,14 @@ pub struct ScriptRedeemer {
 #[serde(rename_all = "snake_case")]
 pub enum ScriptType {
     Timelock,
+    /// Plutus script of an unknown version, returned by older API versions.
     Plutus,
+    #[serde(rename = "plutusV1")]
+    PlutusV1,
+    #[serde(rename = "plutusV2")]
+    PlutusV2,
+    #[serde(rename = "plutusV3")]
+    PlutusV3,
 }
 
 /// Inner enum for [`ScriptRedeemer`].
@@ -101,6 +152,53 @@ mod tests {
     }
     "# }
 
+    test_example! { test_scripts_by_id_plutus_v1, Script, r#"
+    {
+      "script_hash": "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656",
+      "type": "plutusV1",
+      "serialised_size": 14
+    }
+    "# }
+
+    test_example! { test_scripts_json, ScriptJson, r#"
+    {
+      "json": {
+        "type": "atLeast",
+        "scripts": [
+          {
+            "type": "sig",
+            "keyHash": "654891a4db2ea44b5263f4079a33efa0358ba90769e3d8f86a4a0f81"
+          },
+          {
+            "type": "sig",
+            "keyHash": "8685ad48f9bebb8fdb6447abbe140645e0bf743ff98da62e63e2147f"
+          }
+        ],
+        "required": 1
+      }
+    }
+    "# }
+
+    test_example! { test_scripts_cbor, ScriptCbor, r#"
+    {
+      "cbor": "4e4d01000033222220051200120011"
+    }
+    "# }
+
+    test_example! { test_scripts_datum, ScriptDatum, r#"
+    {
+      "json_value": {
+        "int": 42
+      }
+    }
+    "# }
+
+    test_example! { test_scripts_datum_cbor, ScriptDatumCbor, r#"
+    {
+      "cbor": "19a6aa"
+    }
+    "# }
+
     test_example! { test_scripts_redeemers, Vec<ScriptRedeemer>, r#"
     [
       {
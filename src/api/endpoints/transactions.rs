@@ -1,17 +1,49 @@
+use std::{
+    sync::PoisonError,
+    time::{Duration, Instant},
+};
+
 use reqwest::{header::HeaderValue, Body, Method};
 use serde::{Deserialize, Serialize};
 use serde_json::from_str as json_from;
 
-use crate::{request::send_request, url::Url, *};
+use crate::{
+    request::send_request,
+    submit::{SignedTransaction, TxPayload},
+    url::Url,
+    *,
+};
+
+// How long a fetched `max_tx_size` is used, it only changes at epoch boundaries
+const MAX_TX_SIZE_TTL: Duration = Duration::from_secs(3600);
 
 impl BlockFrostApi {
-    /// Obtain information about Move Instantaneous Rewards (MIRs) of a specific transaction.
+    /// Submit an already serialized transaction to the network.
+    ///
+    /// The transaction is given as CBOR bytes, hex or a `cardano-cli` TextEnvelope. Before it's
+    /// sent, it's checked locally, see [`TxPayload::decode`], and against the `max_tx_size` of the
+    /// [`epochs_latest_parameters`](BlockFrostApi::epochs_latest_parameters), fetched at most once
+    /// an hour. The returned hash is checked against the one computed locally, a mismatch is an
+    /// [`Error::SubmittedHashMismatch`], the transaction was submitted anyway.
     ///
-    /// OpenAPI endpoint reference: [`/accounts/{stake_address}/mirs`].
+    /// OpenAPI endpoint reference: [`/tx/submit`].
     ///
-    /// [`/accounts/{stake_address}/mirs`]: https://docs.blockfrost.io/#tag/Cardano-Transactions/paths/~1tx~1submit/post
-    pub async fn transactions_submit(&self, transaction_data: Vec<u8>) -> crate::Result<String> {
-        let body = Body::from(transaction_data);
+    /// [`/tx/submit`]: https://docs.blockfrost.io/#tag/Cardano-Transactions/paths/~1tx~1submit/post
+    pub async fn transactions_submit(
+        &self,
+        transaction: impl Into<TxPayload>,
+    ) -> crate::Result<TxHash> {
+        self.submit_signed(transaction.into().decode()?).await
+    }
+
+    // Submit a decoded transaction, checking its size and the returned hash
+    pub(crate) async fn submit_signed(
+        &self,
+        transaction: SignedTransaction,
+    ) -> crate::Result<TxHash> {
+        transaction.check_size(self.max_tx_size().await?)?;
+
+        let body = Body::from(transaction.as_bytes().to_vec());
         let content_type_header = ("Content-Type", HeaderValue::from_static("application/cbor"));
 
         let endpoint_suffix = "/tx/submit";
@@ -30,7 +62,29 @@ impl BlockFrostApi {
         if !status.is_success() {
             return Err(process_error_response(&text, status, &url));
         }
-        json_from(&text).map_err(|reason| json_error(url, text, reason))
+        let returned: String = json_from(&text).map_err(|reason| json_error(url, text, reason))?;
+        if transaction.check_hash(&returned).is_err() {
+            return Err(Error::SubmittedHashMismatch {
+                submitted: transaction.hash().clone(),
+                returned,
+            });
+        }
+        Ok(transaction.hash().clone())
+    }
+
+    // `max_tx_size` of the latest epoch parameters, cached for `MAX_TX_SIZE_TTL`
+    async fn max_tx_size(&self) -> crate::Result<i64> {
+        let cached = *self.max_tx_size.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((fetched_at, max_tx_size)) = cached {
+            if fetched_at.elapsed() < MAX_TX_SIZE_TTL {
+                return Ok(max_tx_size);
+            }
+        }
+
+        let max_tx_size = self.epochs_latest_parameters().await?.max_tx_size;
+        *self.max_tx_size.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((Instant::now(), max_tx_size));
+        Ok(max_tx_size)
     }
 
     endpoints! {
@@ -38,6 +92,10 @@ impl BlockFrostApi {
         transaction_by_hash(hash: &str) -> Transaction => "/txs/{hash}";
             ("https://docs.blockfrost.io/#tag/Cardano-Transactions/paths/~1txs~1{hash}/get"),
 
+        /// CBOR serialized transaction, see [`TransactionCbor::decoded`].
+        transactions_cbor(hash: &str) -> TransactionCbor => "/txs/{hash}/cbor";
+            ("https://docs.blockfrost.io/#tag/Cardano-Transactions/paths/~1txs~1{hash}~1cbor/get"),
+
         /// Return the inputs and UTXOs of the specific transaction.
         transactions_utxos(hash: &str) -> TransactionUtxos => "/txs/{hash}/utxos";
             ("https://docs.blockfrost.io/#tag/Cardano-Transactions/paths/~1txs~1{hash}~1utxos/get"),
@@ -84,9 +142,9 @@ impl BlockFrostApi {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Transaction {
     /// Transaction hash.
-    pub hash: String,
+    pub hash: TxHash,
     /// Block hash.
-    pub block: String,
+    pub block: BlockHash,
     /// Block number.
     pub block_height: Integer,
     /// Block creation time in UNIX time.
@@ -127,11 +185,18 @@ pub struct Transaction {
     pub redeemer_count: Integer,
 }
 
+/// Created by [`transactions_cbor`](BlockFrostApi::transactions_cbor) method.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionCbor {
+    /// Hex-encoded CBOR of the transaction.
+    pub cbor: String,
+}
+
 /// Created by [`transactions_utxos`](BlockFrostApi::transactions_utxos) method.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TransactionUtxos {
     /// Transaction hash.
-    pub hash: String,
+    pub hash: TxHash,
     pub inputs: Vec<TransactionUtxosInput>,
     pub outputs: Vec<TransactionUtxosOutput>,
 }
@@ -157,7 +222,7 @@ pub struct TransactionDelegation {
     /// Bech32 delegation stake address.
     pub address: String,
     /// Bech32 ID of delegated stake pool.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Epoch in which the delegation becomes active.
     pub active_epoch: Integer,
 }
@@ -190,7 +255,7 @@ pub struct TransactionPoolUpdate {
     /// Index of the certificate within the transaction
     pub cert_index: Integer,
     /// Bech32 encoded pool ID
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// VRF key hash
     pub vrf_key: String,
     /// Stake pool certificate pledge in Lovelaces
@@ -215,7 +280,7 @@ pub struct TransactionPoolRetire {
     /// Index of the certificate within the transaction.
     pub cert_index: Integer,
     /// Bech32 stake pool ID.
-    pub pool_id: String,
+    pub pool_id: PoolId,
     /// Retiring epoch.
     pub retiring_epoch: Integer,
 }
@@ -262,7 +327,7 @@ pub struct TransactionUtxosInput {
     pub address: String,
     pub amount: Vec<Amount>,
     /// Hash of the UTXO transaction.
-    pub tx_hash: String,
+    pub tx_hash: TxHash,
     /// UTXO index in the transaction.
     pub output_index: Integer,
     /// The hash of the transaction output datum.
@@ -279,6 +344,10 @@ pub struct TransactionUtxosOutput {
     pub amount: Vec<Amount>,
     /// The hash of the transaction output datum.
     pub data_hash: Option<String>,
+    /// CBOR encoded inline datum.
+    pub inline_datum: Option<String>,
+    /// The hash of the reference script of the output.
+    pub reference_script_hash: Option<String>,
     /// UTXO index in the transaction.
     pub output_index: Integer,
 }
@@ -346,6 +415,7 @@ mod tests {
       "hash": "1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477",
       "block": "356b7d7dbb696ccd12775c016941057a9dc70898d87a63fc752271bb46856940",
       "block_height": 123456,
+      "block_time": 1635505891,
       "slot": 42000000,
       "index": 1,
       "output_amount": [
@@ -409,6 +479,12 @@ mod tests {
       "valid_contract": true
     }"# }
 
+    test_example! { test_transactions_cbor, TransactionCbor, r#"
+    {
+      "cbor": "84a3008001800200a0f5f6"
+    }
+    "# }
+
     test_example! { test_transaction_utxos, TransactionUtxos, r#"
     {
       "hash": "1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477",
@@ -437,20 +513,36 @@ mod tests {
           "amount": [
             {
               "unit": "lovelace",
-              "quantity": "42000000",
-              "data_hash": null
+              "quantity": "42000000"
             },
             {
               "unit": "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a76e7574636f696e",
-              "quantity": "12",
-              "data_hash": "9e478573ab81ea7a8e31891ce0648b81229f408d596a3483e6f4f9b92d3cf710"
+              "quantity": "12"
             }
-          ]
+          ],
+          "output_index": 0,
+          "data_hash": "9e478573ab81ea7a8e31891ce0648b81229f408d596a3483e6f4f9b92d3cf710"
         }
       ]
     }
     "# }
 
+    test_example! { test_transaction_utxos_output, TransactionUtxosOutput, r#"
+    {
+      "address": "addr1q9ld26v2lv8wvrxxmvg90pn8n8n5k6tdst06q2s856rwmvnueldzuuqmnsye359fqrk8hwvenjnqultn7djtrlft7jnq7dy7wv",
+      "amount": [
+        {
+          "unit": "lovelace",
+          "quantity": "42000000"
+        }
+      ],
+      "output_index": 0,
+      "data_hash": "9e1199a988ba72ffd6e9c269cadb3b53b5f360ff99f112d9b2ee30c4d74ad88b",
+      "inline_datum": "182a",
+      "reference_script_hash": "13a3efd825703a352a8f71f4e2758d08c28c564e8dfcce9f77776ad1"
+    }
+    "# }
+
     test_example! { test_transaction_stakes, Vec<TransactionStake>, r#"
     [
       {
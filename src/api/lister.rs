@@ -40,7 +40,7 @@ impl<'api, T: 'api + for<'de> serde::Deserialize<'de>> Stream for Lister<'api, T
     fn poll_next(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
         while self.inner.len() < 10 {
             // Making the next requests
-            let BlockFrostApi { settings, client } = &self.api;
+            let BlockFrostApi { settings, client, .. } = &self.api;
             let page = Some(self.current_page);
 
             let Url(url) = Url::from_endpoint_with_page(settings, &self.endpoint, page);
@@ -2,13 +2,21 @@
 pub(super) mod endpoints;
 pub(super) mod lister;
 
-use std::future::Future;
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use reqwest::ClientBuilder;
 
 use crate::{
-    request::send_get_request, url::Url, utils::build_header_map,
-    utils::create_client_with_project_id, BlockFrostSettings,
+    request::{send_get_request, send_get_request_with_meta},
+    stream::Lister,
+    url::Url,
+    utils::build_header_map,
+    utils::create_client_with_project_id,
+    BlockFrostSettings, JsonValue, WithMeta,
 };
 
 /// Provides methods for making requests to the [BlockFrost API](https://docs.blockfrost.io).
@@ -16,6 +24,8 @@ use crate::{
 pub struct BlockFrostApi {
     pub settings: BlockFrostSettings,
     client: reqwest::Client,
+    // Cached `max_tx_size` protocol parameter and when it was fetched, shared by clones
+    pub(crate) max_tx_size: Arc<Mutex<Option<(Instant, i64)>>>,
 }
 
 impl BlockFrostApi {
@@ -30,7 +40,7 @@ impl BlockFrostApi {
     /// [`HeaderValue::from_str`]: reqwest::header::HeaderValue::from_str
     pub fn new(project_id: impl AsRef<str>, settings: BlockFrostSettings) -> Self {
         let client = create_client_with_project_id(project_id.as_ref());
-        Self { settings, client }
+        Self { settings, client, max_tx_size: Arc::default() }
     }
 
     /// Create a [`BlockFrostApi`] with [custom settings](BlockFrostSettings) and [custom client](ClientBuilder).
@@ -58,18 +68,79 @@ impl BlockFrostApi {
         client_builder
             .default_headers(build_header_map(project_id.as_ref()))
             .build()
-            .map(|client| Self { settings, client })
+            .map(|client| Self { settings, client, max_tx_size: Arc::default() })
     }
 
-    // Url endpoint example: "/blocks"
-    fn get_from_endpoint<T>(
-        &self,
-        url_endpoint: &str,
-    ) -> impl Future<Output = crate::Result<T>> + Send
+    /// Make a GET request to any endpoint, deserializing the response into `T`.
+    ///
+    /// Useful for endpoints that are not wrapped by this crate yet, the request is sent with the
+    /// same headers, [retry settings](crate::RetrySettings), [query parameters](crate::QueryParameters)
+    /// and error handling as every other method.
+    ///
+    /// The `endpoint` is appended to the network address, so it should start with a slash, e.g.
+    /// `"/blocks/latest"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blockfrost::{BlockFrostApi, Block};
+    ///
+    /// async fn latest_block(api: &BlockFrostApi) -> blockfrost::Result<Block> {
+    ///     api.get::<Block>("/blocks/latest").await
+    /// }
+    /// ```
+    pub fn get<T>(&self, endpoint: &str) -> impl Future<Output = crate::Result<T>> + Send
     where
         T: serde::de::DeserializeOwned,
     {
-        let Url(url) = Url::from_endpoint(&self.settings, url_endpoint);
+        let Url(url) = Url::from_endpoint(&self.settings, endpoint);
         send_get_request(&self.client, url, self.settings.retry_settings)
     }
+
+    /// Make a GET request to any endpoint, returning the body together with the response metadata.
+    ///
+    /// Same as [`BlockFrostApi::get`], see [`WithMeta`] for the available metadata.
+    pub fn get_with_meta<T>(
+        &self,
+        endpoint: &str,
+    ) -> impl Future<Output = crate::Result<WithMeta<T>>> + Send
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let Url(url) = Url::from_endpoint(&self.settings, endpoint);
+        send_get_request_with_meta(&self.client, url, self.settings.retry_settings)
+    }
+
+    /// Make a GET request to any endpoint, returning the untyped [`JsonValue`].
+    ///
+    /// Same as [`BlockFrostApi::get`], but skips the typed deserialization.
+    pub fn get_json(
+        &self,
+        endpoint: &str,
+    ) -> impl Future<Output = crate::Result<JsonValue>> + Send {
+        self.get(endpoint)
+    }
+
+    /// Create a [`Lister`] for any paginated endpoint, `T` is the type of a single page.
+    ///
+    /// Same as the `_all` variant of the endpoint methods, see [`BlockFrostApi::get`] for the
+    /// format of `endpoint`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blockfrost::{stream::StreamExt, BlockFrostApi};
+    ///
+    /// async fn list_txs(api: &BlockFrostApi) -> blockfrost::Result<()> {
+    ///     let mut lister = api.list::<Vec<String>>("/blocks/latest/txs").take(3);
+    ///
+    ///     while let Some(page) = lister.next().await {
+    ///         dbg!(page?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list<T>(&self, endpoint: impl Into<String>) -> Lister<'_, T> {
+        Lister::list_from_endpoint(self, endpoint.into())
+    }
 }
@@ -0,0 +1,403 @@
+//! Internal CBOR decoder and encoder (RFC 8949), used by the local helpers of this crate.
+
+use crate::ParseError;
+
+pub(crate) type Result<T> = std::result::Result<T, ParseError>;
+
+// Nesting limit of skipped items, so malicious input can't overflow the stack
+const MAX_DEPTH: usize = 256;
+
+pub(crate) fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("cbor", reason)
+}
+
+/// Initial byte of a data item, with its argument.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Header {
+    Unsigned(u64),
+    Negative(u64),
+    // Length is None for indefinite-length items
+    Bytes(Option<u64>),
+    Text(Option<u64>),
+    Array(Option<u64>),
+    Map(Option<u64>),
+    Tag(u64),
+    Simple(u8),
+    Float(f64),
+    Break,
+}
+
+pub(crate) struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    /// Offset of the next item, to slice the encoding of the items read since.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    fn take(&mut self, amount: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(amount).filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or_else(|| error("unexpected end of input"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn take_u64(&mut self, amount: usize) -> Result<u64> {
+        Ok(self.take(amount)?.iter().fold(0, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    pub(crate) fn peek_header(&mut self) -> Result<Header> {
+        let position = self.position;
+        let header = self.header();
+        self.position = position;
+        header
+    }
+
+    pub(crate) fn header(&mut self) -> Result<Header> {
+        let initial = self.take(1)?[0];
+        let (major, additional) = (initial >> 5, initial & 0x1f);
+
+        if major == 7 {
+            return match additional {
+                0..=23 => Ok(Header::Simple(additional)),
+                24 => Ok(Header::Simple(self.take(1)?[0])),
+                25 => Ok(Header::Float(half_to_f64(self.take_u64(2)? as u16))),
+                26 => Ok(Header::Float(f32::from_bits(self.take_u64(4)? as u32) as f64)),
+                27 => Ok(Header::Float(f64::from_bits(self.take_u64(8)?))),
+                31 => Ok(Header::Break),
+                _ => Err(error(format!("reserved simple value {}", additional))),
+            };
+        }
+
+        let argument = match additional {
+            0..=23 => Some(additional as u64),
+            24 => Some(self.take_u64(1)?),
+            25 => Some(self.take_u64(2)?),
+            26 => Some(self.take_u64(4)?),
+            27 => Some(self.take_u64(8)?),
+            31 => None,
+            _ => return Err(error(format!("reserved additional information {}", additional))),
+        };
+
+        match (major, argument) {
+            (0, Some(value)) => Ok(Header::Unsigned(value)),
+            (1, Some(value)) => Ok(Header::Negative(value)),
+            (2, length) => Ok(Header::Bytes(length)),
+            (3, length) => Ok(Header::Text(length)),
+            (4, length) => Ok(Header::Array(length)),
+            (5, length) => Ok(Header::Map(length)),
+            (6, Some(tag)) => Ok(Header::Tag(tag)),
+            _ => Err(error(format!("invalid indefinite length for major type {}", major))),
+        }
+    }
+
+    pub(crate) fn unsigned(&mut self) -> Result<u64> {
+        match self.header()? {
+            Header::Unsigned(value) => Ok(value),
+            other => Err(unexpected("unsigned integer", other)),
+        }
+    }
+
+    /// Definite or indefinite (chunked) byte string.
+    pub(crate) fn bytes(&mut self) -> Result<Vec<u8>> {
+        match self.header()? {
+            Header::Bytes(Some(length)) => Ok(self.take(to_usize(length)?)?.to_vec()),
+            Header::Bytes(None) => {
+                let mut bytes = Vec::new();
+                while !self.consume_break()? {
+                    match self.header()? {
+                        Header::Bytes(Some(length)) => {
+                            bytes.extend_from_slice(self.take(to_usize(length)?)?)
+                        }
+                        other => return Err(unexpected("byte string chunk", other)),
+                    }
+                }
+                Ok(bytes)
+            }
+            other => Err(unexpected("byte string", other)),
+        }
+    }
+
+    /// Definite or indefinite (chunked) UTF-8 text string.
+    pub(crate) fn text(&mut self) -> Result<String> {
+        let bytes = match self.header()? {
+            Header::Text(Some(length)) => self.take(to_usize(length)?)?.to_vec(),
+            Header::Text(None) => {
+                let mut bytes = Vec::new();
+                while !self.consume_break()? {
+                    match self.header()? {
+                        Header::Text(Some(length)) => {
+                            bytes.extend_from_slice(self.take(to_usize(length)?)?)
+                        }
+                        other => return Err(unexpected("text string chunk", other)),
+                    }
+                }
+                bytes
+            }
+            other => return Err(unexpected("text string", other)),
+        };
+        String::from_utf8(bytes).map_err(|_| error("text string is not UTF-8"))
+    }
+
+    /// Array length, `None` if indefinite.
+    pub(crate) fn array(&mut self) -> Result<Option<u64>> {
+        match self.header()? {
+            Header::Array(length) => Ok(length),
+            other => Err(unexpected("array", other)),
+        }
+    }
+
+    /// Map length, `None` if indefinite.
+    pub(crate) fn map(&mut self) -> Result<Option<u64>> {
+        match self.header()? {
+            Header::Map(length) => Ok(length),
+            other => Err(unexpected("map", other)),
+        }
+    }
+
+    pub(crate) fn tag(&mut self) -> Result<u64> {
+        match self.header()? {
+            Header::Tag(tag) => Ok(tag),
+            other => Err(unexpected("tag", other)),
+        }
+    }
+
+    /// Consumes a break if it's the next item, used for indefinite-length items.
+    pub(crate) fn consume_break(&mut self) -> Result<bool> {
+        if self.peek_header()? == Header::Break {
+            self.position += 1;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Whether there are more entries in an array or map of the given length.
+    pub(crate) fn has_next(&mut self, length: Option<u64>, index: u64) -> Result<bool> {
+        match length {
+            Some(length) => Ok(index < length),
+            None => Ok(!self.consume_break()?),
+        }
+    }
+
+    /// Skips a whole data item, including nested ones up to 256 levels.
+    pub(crate) fn skip(&mut self) -> Result<()> {
+        self.skip_nested(0)
+    }
+
+    fn skip_nested(&mut self, depth: usize) -> Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(error("item is nested too deeply"));
+        }
+
+        match self.header()? {
+            Header::Bytes(Some(length)) | Header::Text(Some(length)) => {
+                self.take(to_usize(length)?)?;
+            }
+            Header::Bytes(None) | Header::Text(None) => {
+                while !self.consume_break()? {
+                    self.skip_nested(depth + 1)?;
+                }
+            }
+            Header::Array(length) => {
+                let mut index = 0;
+                while self.has_next(length, index)? {
+                    self.skip_nested(depth + 1)?;
+                    index += 1;
+                }
+            }
+            Header::Map(length) => {
+                let mut index = 0;
+                while self.has_next(length, index)? {
+                    self.skip_nested(depth + 1)?;
+                    self.skip_nested(depth + 1)?;
+                    index += 1;
+                }
+            }
+            Header::Tag(_) => self.skip_nested(depth + 1)?,
+            Header::Break => return Err(error("unexpected break")),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Encoder of definite-length items, with the shortest argument encodings.
+#[derive(Default)]
+pub(crate) struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    fn header(&mut self, major: u8, argument: u64) -> &mut Self {
+        let major = major << 5;
+        match argument {
+            0..=23 => self.bytes.push(major | argument as u8),
+            24..=0xff => self.bytes.extend([major | 24, argument as u8]),
+            0x100..=0xffff => {
+                self.bytes.push(major | 25);
+                self.bytes.extend((argument as u16).to_be_bytes());
+            }
+            0x1_0000..=0xffff_ffff => {
+                self.bytes.push(major | 26);
+                self.bytes.extend((argument as u32).to_be_bytes());
+            }
+            _ => {
+                self.bytes.push(major | 27);
+                self.bytes.extend(argument.to_be_bytes());
+            }
+        }
+        self
+    }
+
+    pub(crate) fn unsigned(&mut self, value: u64) -> &mut Self {
+        self.header(0, value)
+    }
+
+    /// The negative integer `-1 - value`.
+    pub(crate) fn negative(&mut self, value: u64) -> &mut Self {
+        self.header(1, value)
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.header(2, bytes.len() as u64);
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    /// Start an indefinite-length byte string, of definite-length chunks.
+    pub(crate) fn begin_bytes(&mut self) -> &mut Self {
+        self.bytes.push(0x5f);
+        self
+    }
+
+    pub(crate) fn text(&mut self, text: &str) -> &mut Self {
+        self.header(3, text.len() as u64);
+        self.bytes.extend_from_slice(text.as_bytes());
+        self
+    }
+
+    pub(crate) fn array(&mut self, length: usize) -> &mut Self {
+        self.header(4, length as u64)
+    }
+
+    /// Start an indefinite-length array.
+    pub(crate) fn begin_array(&mut self) -> &mut Self {
+        self.bytes.push(0x9f);
+        self
+    }
+
+    /// End an indefinite-length item.
+    pub(crate) fn end(&mut self) -> &mut Self {
+        self.bytes.push(0xff);
+        self
+    }
+
+    pub(crate) fn map(&mut self, length: usize) -> &mut Self {
+        self.header(5, length as u64)
+    }
+
+    pub(crate) fn tag(&mut self, tag: u64) -> &mut Self {
+        self.header(6, tag)
+    }
+}
+
+pub(crate) fn to_usize(length: u64) -> Result<usize> {
+    usize::try_from(length).map_err(|_| error("length does not fit in memory"))
+}
+
+pub(crate) fn unexpected(expected: &str, found: Header) -> ParseError {
+    error(format!("expected {}, found {:?}", expected, found))
+}
+
+fn half_to_f64(half: u16) -> f64 {
+    let exponent = (half >> 10) & 0x1f;
+    let mantissa = (half & 0x3ff) as f64;
+    let value = match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (mantissa + 1024.0) * 2f64.powi(exponent as i32 - 25),
+    };
+    if half & 0x8000 != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_headers() {
+        // Examples from RFC 8949, appendix A
+        let cases: &[(&[u8], Header)] = &[
+            (&[0x17], Header::Unsigned(23)),
+            (&[0x19, 0x03, 0xe8], Header::Unsigned(1000)),
+            (&[0x38, 0x63], Header::Negative(99)),
+            (&[0xf9, 0x3c, 0x00], Header::Float(1.0)),
+            (&[0xf9, 0xc4, 0x00], Header::Float(-4.0)),
+            (&[0xf4], Header::Simple(20)),
+            (&[0x9f], Header::Array(None)),
+            (&[0xc2], Header::Tag(2)),
+        ];
+        for (bytes, header) in cases {
+            assert_eq!(Decoder::new(bytes).header().unwrap(), *header);
+        }
+    }
+
+    #[test]
+    fn test_encode_headers() {
+        let mut encoder = Encoder::new();
+        encoder.unsigned(23).unsigned(24).unsigned(1000).unsigned(1_000_000).unsigned(u64::MAX);
+        encoder.bytes(&[1, 2]).array(3).map(0).tag(24);
+        let expected = [
+            &[0x17, 0x18, 0x18, 0x19, 0x03, 0xe8, 0x1a, 0x00, 0x0f, 0x42, 0x40][..],
+            &[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            &[0x42, 0x01, 0x02, 0x83, 0xa0, 0xd8, 0x18],
+        ]
+        .concat();
+        assert_eq!(encoder.into_bytes(), expected);
+    }
+
+    #[test]
+    fn test_chunked_bytes_and_skip() {
+        // (_ h'0102', h'030405')
+        let bytes = [0x5f, 0x42, 0x01, 0x02, 0x43, 0x03, 0x04, 0x05, 0xff];
+        assert_eq!(Decoder::new(&bytes).bytes().unwrap(), [1, 2, 3, 4, 5]);
+
+        // {_ "a": 1, "b": [_ 2, 3]}
+        let bytes = [0xbf, 0x61, 0x61, 0x01, 0x61, 0x62, 0x9f, 0x02, 0x03, 0xff, 0xff];
+        let mut decoder = Decoder::new(&bytes);
+        decoder.skip().unwrap();
+        assert!(decoder.is_finished());
+
+        assert!(Decoder::new(&[0x82, 0x01]).skip().is_err());
+
+        let nested = [vec![0x81; MAX_DEPTH], vec![0x00]].concat();
+        assert!(Decoder::new(&nested).skip().is_ok());
+        let too_nested = [vec![0x81; 200_000], vec![0x00]].concat();
+        assert!(Decoder::new(&too_nested).skip().is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! Typed [CIP-20] transaction messages, with the [CIP-83] encryption.
+//!
+//! Messages, e.g. payment memos, are lines of text in the `msg` array of the metadata label
+//! [`CIP20_LABEL`]. [`BlockFrostApi::cip20_message`] reads the message of a transaction.
+//!
+//! [CIP-20]: https://cips.cardano.org/cips/cip20/
+//! [CIP-83]: https://cips.cardano.org/cips/cip83/
+
+use aes::Aes256;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use cbc::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use serde_json::json;
+use sha2::Sha256;
+
+use crate::{tx_metadata::TxMetadatum, BlockFrostApi, JsonValue, ParseError};
+
+/// Metadata label of transaction messages.
+pub const CIP20_LABEL: u64 = 674;
+
+/// Passphrase of encrypted messages that don't set their own, defined by CIP-83.
+pub const DEFAULT_PASSPHRASE: &str = "cardano";
+
+// The only CIP-83 method: OpenSSL-compatible AES-256-CBC with a PBKDF2-HMAC-SHA256 key
+const BASIC_ENCRYPTION: &str = "basic";
+const SALT_HEADER: &[u8] = b"Salted__";
+const PBKDF2_ROUNDS: u32 = 10_000;
+
+/// A CIP-20 message, the value of the metadata label [`CIP20_LABEL`].
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::cip20::{Cip20Message, DEFAULT_PASSPHRASE};
+///
+/// let message = Cip20Message::from_text("Invoice-No: 1234\nThanks!");
+/// assert_eq!(message.lines().unwrap(), ["Invoice-No: 1234", "Thanks!"]);
+///
+/// let salt = [1, 2, 3, 4, 5, 6, 7, 8]; // Random in practice
+/// let encrypted = message.encrypt(DEFAULT_PASSPHRASE, salt).unwrap();
+/// assert_eq!(encrypted.decrypt(DEFAULT_PASSPHRASE).unwrap(), message.lines().unwrap());
+///
+/// // Value to attach under the label 674 of an outgoing transaction
+/// let metadatum = encrypted.to_metadatum();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cip20Message {
+    /// Lines of text, each up to 64 bytes.
+    Plain(Vec<String>),
+    /// Message encrypted with CIP-83, see [`decrypt`](Cip20Message::decrypt).
+    Encrypted {
+        /// Encryption method, `"basic"` is the only one defined.
+        method: String,
+        /// The base64 chunks of the `msg` array, joined.
+        payload: String,
+    },
+}
+
+impl Cip20Message {
+    /// Create a plain message from text, one line per line of `text`, lines longer than 64 bytes
+    /// are split.
+    pub fn from_text(text: &str) -> Self {
+        let lines = text.lines().flat_map(|line| match TxMetadatum::text_chunks(line) {
+            TxMetadatum::List(chunks) if !chunks.is_empty() => {
+                chunks.into_iter().filter_map(|chunk| chunk.as_text().map(str::to_owned)).collect()
+            }
+            _ => vec![String::new()],
+        });
+        Cip20Message::Plain(lines.collect())
+    }
+
+    /// Parse the JSON metadata of the label, as returned by
+    /// [`transactions_metadata`](BlockFrostApi::transactions_metadata).
+    ///
+    /// A `msg` string instead of an array is accepted as a single line.
+    pub fn from_json(json: &JsonValue) -> Result<Self, ParseError> {
+        let lines = match json.get("msg") {
+            Some(JsonValue::Array(lines)) => lines
+                .iter()
+                .map(|line| line.as_str().map(str::to_owned))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| error("'msg' has lines that are not strings"))?,
+            Some(JsonValue::String(line)) => vec![line.clone()],
+            _ => return Err(error("no 'msg' array of strings")),
+        };
+
+        match json.get("enc") {
+            None => Ok(Cip20Message::Plain(lines)),
+            Some(JsonValue::String(method)) => {
+                Ok(Cip20Message::Encrypted { method: method.clone(), payload: lines.concat() })
+            }
+            Some(other) => Err(error(format!("encryption method {} is not a string", other))),
+        }
+    }
+
+    /// Lines of a plain message, `None` if it's encrypted.
+    pub fn lines(&self) -> Option<&[String]> {
+        match self {
+            Cip20Message::Plain(lines) => Some(lines),
+            Cip20Message::Encrypted { .. } => None,
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self, Cip20Message::Encrypted { .. })
+    }
+
+    /// Lines of the message, decrypted with `passphrase` if it's encrypted, try
+    /// [`DEFAULT_PASSPHRASE`] when the sender didn't share one.
+    ///
+    /// The payload is what `openssl enc -aes-256-cbc -pbkdf2 -iter 10000 -md sha256` outputs in
+    /// base64, for the JSON of the `msg` array.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<String>, ParseError> {
+        let payload = match self {
+            Cip20Message::Plain(lines) => return Ok(lines.clone()),
+            Cip20Message::Encrypted { method, .. } if method != BASIC_ENCRYPTION => {
+                return Err(error(format!("encryption method '{}' is not supported", method)))
+            }
+            Cip20Message::Encrypted { payload, .. } => payload,
+        };
+
+        let bytes = BASE64.decode(payload).map_err(|_| error("payload is not base64"))?;
+        let salted = bytes.strip_prefix(SALT_HEADER).filter(|salted| salted.len() >= 8);
+        let (salt, ciphertext) = salted.ok_or_else(|| error("payload has no salt"))?.split_at(8);
+
+        let (key, iv) = derive_key_iv(passphrase, salt);
+        let plaintext = cbc::Decryptor::<Aes256>::new(&key.into(), &iv.into())
+            .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+            .map_err(|_| error("wrong passphrase or corrupted payload"))?;
+
+        // The JSON of `{ "msg": [...] }`, or of the array alone
+        let json: JsonValue = serde_json::from_slice(&plaintext)
+            .map_err(|_| error("decrypted payload is not JSON"))?;
+        let json = if json.is_object() { json } else { json!({ "msg": json }) };
+        match Self::from_json(&json)? {
+            Cip20Message::Plain(lines) => Ok(lines),
+            Cip20Message::Encrypted { .. } => Err(error("decrypted payload is encrypted again")),
+        }
+    }
+
+    /// Encrypt a plain message with the CIP-83 `basic` method, the `salt` must be random.
+    ///
+    /// Fails if the message is already encrypted.
+    pub fn encrypt(&self, passphrase: &str, salt: [u8; 8]) -> Result<Self, ParseError> {
+        let lines = self.lines().ok_or_else(|| error("message is already encrypted"))?;
+        let plaintext = json!({ "msg": lines }).to_string();
+
+        let (key, iv) = derive_key_iv(passphrase, &salt);
+        let ciphertext = cbc::Encryptor::<Aes256>::new(&key.into(), &iv.into())
+            .encrypt_padded_vec_mut::<Pkcs7>(plaintext.as_bytes());
+
+        let payload = BASE64.encode([SALT_HEADER, &salt, &ciphertext].concat());
+        Ok(Cip20Message::Encrypted { method: BASIC_ENCRYPTION.to_owned(), payload })
+    }
+
+    /// The metadata value for the label [`CIP20_LABEL`] of a transaction, the encrypted payload
+    /// is split into 64 bytes lines.
+    pub fn to_metadatum(&self) -> TxMetadatum {
+        let text = |text: &str| TxMetadatum::Text(text.to_owned());
+
+        match self {
+            Cip20Message::Plain(lines) => TxMetadatum::Map(vec![(
+                text("msg"),
+                TxMetadatum::List(lines.iter().map(|line| text(line)).collect()),
+            )]),
+            Cip20Message::Encrypted { method, payload } => TxMetadatum::Map(vec![
+                (text("enc"), text(method)),
+                (text("msg"), TxMetadatum::text_chunks(payload)),
+            ]),
+        }
+    }
+}
+
+impl BlockFrostApi {
+    /// Fetch the CIP-20 message of a transaction, `None` if it has none.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// use blockfrost::cip20::DEFAULT_PASSPHRASE;
+    ///
+    /// let hash = "1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477";
+    /// if let Some(message) = api.cip20_message(hash).await? {
+    ///     println!("{}", message.decrypt(DEFAULT_PASSPHRASE)?.join("\n"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cip20_message(&self, hash: &str) -> crate::Result<Option<Cip20Message>> {
+        let label = CIP20_LABEL.to_string();
+        let metadata = self.transactions_metadata(hash).await?;
+
+        match metadata.iter().find(|metadata| metadata.label == label) {
+            Some(metadata) => Ok(Some(Cip20Message::from_json(&metadata.json_metadata)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+// 32 bytes of key and 16 bytes of IV, as `openssl enc -pbkdf2` derives them
+fn derive_key_iv(passphrase: &str, salt: &[u8]) -> ([u8; 32], [u8; 16]) {
+    let mut key_iv = [0; 48];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key_iv);
+
+    let (mut key, mut iv) = ([0; 32], [0; 16]);
+    key.copy_from_slice(&key_iv[..32]);
+    iv.copy_from_slice(&key_iv[32..]);
+    (key, iv)
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("cip-20 message", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `openssl enc -aes-256-cbc -pbkdf2 -iter 10000 -md sha256 -pass pass:cardano -base64`
+    const ENCRYPTED: &str = "U2FsdGVkX1+YLSHscigaBZ51erXJ78SDnncJ9FgW4y8/WCTKhKyZsM6BxGxQ87rOBNkWPa8yQQYPT1jfXlKUNn+u0dCBBxZfjmGUn0m0fO24HVo+iDanfqBOc5cILXSS";
+    const LINES: [&str; 3] = ["Invoice-No: 123456789", "Order-No: 7654321", "Email: john@doe.com"];
+
+    #[test]
+    fn test_plain_message() {
+        let json = json!({ "msg": LINES });
+        let message = Cip20Message::from_json(&json).unwrap();
+
+        assert_eq!(message.lines().unwrap(), LINES);
+        assert_eq!(message.decrypt("ignored").unwrap(), LINES);
+        assert_eq!(message.to_metadatum().to_json_no_schema(), json);
+
+        let long = Cip20Message::from_text(&format!("{}\n\nend", "a".repeat(70)));
+        assert_eq!(long.lines().unwrap(), ["a".repeat(64), "a".repeat(6), "".into(), "end".into()]);
+        assert!(Cip20Message::from_json(&json!({ "msg": [1] })).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_openssl_message() {
+        let json = json!({ "enc": "basic", "msg": [&ENCRYPTED[..64], &ENCRYPTED[64..]] });
+        let message = Cip20Message::from_json(&json).unwrap();
+
+        assert!(message.is_encrypted());
+        assert_eq!(message.decrypt(DEFAULT_PASSPHRASE).unwrap(), LINES);
+        assert!(message.decrypt("wrong").is_err());
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let salt = [0x98, 0x2d, 0x21, 0xec, 0x72, 0x28, 0x1a, 0x05];
+        let message = Cip20Message::Plain(LINES.map(str::to_owned).to_vec());
+        let encrypted = message.encrypt(DEFAULT_PASSPHRASE, salt).unwrap();
+
+        let expected = json!({ "enc": "basic", "msg": [&ENCRYPTED[..64], &ENCRYPTED[64..]] });
+        assert_eq!(encrypted.to_metadatum().to_json_no_schema(), expected);
+        assert!(encrypted.encrypt(DEFAULT_PASSPHRASE, salt).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! Typed [CIP-25] NFT metadata, as found in [`AssetDetails::onchain_metadata`] or under the
+//! transaction metadata label `721`.
+//!
+//! Strings longer than 64 bytes can't be stored on chain, so CIP-25 allows splitting them into
+//! arrays of strings. [`Cip25Metadata`] joins those chunks back.
+//!
+//! [CIP-25]: https://cips.cardano.org/cips/cip25/
+
+use serde_json::Value as JsonValue;
+
+use crate::{AssetDetails, AssetId, JsonMap, ParseError};
+
+/// Transaction metadata label of CIP-25 NFT metadata.
+pub const CIP25_LABEL: &str = "721";
+
+/// NFT metadata of a single asset.
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::cip25::Cip25Metadata;
+/// use serde_json::json;
+///
+/// let metadata = json!({
+///     "name": "SpaceBud #1507",
+///     "image": ["ipfs://QmUn3W8MwdpLCDfFTtMnw1rtKBjzrYHymtLSBAhB3mpK2z/", "spacebud.png"],
+///     "mediaType": "image/png",
+/// });
+/// let metadata = Cip25Metadata::from_json(&metadata).unwrap();
+///
+/// assert_eq!(metadata.name, "SpaceBud #1507");
+/// assert_eq!(
+///     metadata.image_ipfs_path().unwrap(),
+///     "QmUn3W8MwdpLCDfFTtMnw1rtKBjzrYHymtLSBAhB3mpK2z/spacebud.png"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cip25Metadata {
+    /// Name of the asset.
+    pub name: String,
+    /// URI of the image, chunks joined.
+    pub image: String,
+    /// MIME type of the image.
+    pub media_type: Option<String>,
+    /// Description of the asset, chunks joined.
+    pub description: Option<String>,
+    /// Additional files, such as high resolution images or audio.
+    pub files: Vec<Cip25File>,
+    /// Every other property, such as traits.
+    pub attributes: JsonMap,
+}
+
+/// Inner member of [`Cip25Metadata`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cip25File {
+    /// Name of the file.
+    pub name: Option<String>,
+    /// MIME type of the file.
+    pub media_type: String,
+    /// URI of the file, chunks joined.
+    pub src: String,
+    /// Every other property of the file.
+    pub attributes: JsonMap,
+}
+
+impl Cip25Metadata {
+    /// Parse the metadata of a single asset, such as [`AssetDetails::onchain_metadata`].
+    pub fn from_json(json: &JsonValue) -> Result<Self, ParseError> {
+        let mut map = json.as_object().ok_or_else(|| error("metadata is not an object"))?.clone();
+
+        let name = chunked_string(map.remove("name"), "name")?.ok_or_else(|| missing("name"))?;
+        let image =
+            chunked_string(map.remove("image"), "image")?.ok_or_else(|| missing("image"))?;
+        let media_type = chunked_string(map.remove("mediaType"), "mediaType")?;
+        let description = chunked_string(map.remove("description"), "description")?;
+
+        let files = match map.remove("files") {
+            None => Vec::new(),
+            Some(JsonValue::Array(files)) => {
+                files.iter().map(Cip25File::from_json).collect::<Result<_, _>>()?
+            }
+            Some(_) => return Err(error("'files' is not an array")),
+        };
+
+        Ok(Self { name, image, media_type, description, files, attributes: map })
+    }
+
+    /// Parse the metadata of an asset from the whole content of the label `721`.
+    ///
+    /// Assets are keyed by their UTF-8 name in version 1, and by their hex-encoded name in
+    /// version 2, both are looked up.
+    pub fn from_label_721(json: &JsonValue, asset: &AssetId) -> Result<Self, ParseError> {
+        let policy = json
+            .get(&asset.policy_id)
+            .ok_or_else(|| error(format!("policy '{}' not found", asset.policy_id)))?;
+
+        let utf8_name = String::from_utf8(asset.asset_name_bytes()).ok();
+        let metadata = utf8_name
+            .and_then(|name| policy.get(name))
+            .or_else(|| policy.get(&asset.asset_name))
+            .ok_or_else(|| error(format!("asset '{}' not found", asset.asset_name)))?;
+
+        Self::from_json(metadata)
+    }
+
+    /// IPFS path of the image, to be fetched with [`IpfsApi::gateway`](crate::IpfsApi::gateway).
+    ///
+    /// See [`ipfs_path`].
+    pub fn image_ipfs_path(&self) -> Option<String> {
+        ipfs_path(&self.image)
+    }
+}
+
+impl Cip25File {
+    fn from_json(json: &JsonValue) -> Result<Self, ParseError> {
+        let mut map = json.as_object().ok_or_else(|| error("file is not an object"))?.clone();
+
+        let name = chunked_string(map.remove("name"), "files.name")?;
+        let media_type = chunked_string(map.remove("mediaType"), "files.mediaType")?
+            .ok_or_else(|| missing("files.mediaType"))?;
+        let src =
+            chunked_string(map.remove("src"), "files.src")?.ok_or_else(|| missing("files.src"))?;
+
+        Ok(Self { name, media_type, src, attributes: map })
+    }
+
+    /// IPFS path of the file, see [`ipfs_path`].
+    pub fn ipfs_path(&self) -> Option<String> {
+        ipfs_path(&self.src)
+    }
+}
+
+impl AssetDetails {
+    /// Parse [`onchain_metadata`](AssetDetails::onchain_metadata) as CIP-25 metadata, `None` if
+    /// there's no metadata.
+    pub fn cip25_metadata(&self) -> Option<Result<Cip25Metadata, ParseError>> {
+        let metadata = self.onchain_metadata.clone()?;
+        Some(Cip25Metadata::from_json(&JsonValue::Object(metadata)))
+    }
+}
+
+/// Resolve an URI to the IPFS path (CID and optional path) that
+/// [`IpfsApi::gateway`](crate::IpfsApi::gateway) can fetch.
+///
+/// Accepts `ipfs://<cid>`, the legacy `ipfs://ipfs/<cid>`, HTTP gateway URLs
+/// (`https://<host>/ipfs/<cid>`) and bare CIDs. `None` for other URIs, e.g. `data:` or `ar://`.
+pub fn ipfs_path(uri: &str) -> Option<String> {
+    let uri = uri.trim();
+
+    let path = if let Some(path) = uri.strip_prefix("ipfs://") {
+        path.strip_prefix("ipfs/").unwrap_or(path)
+    } else if uri.starts_with("https://") || uri.starts_with("http://") {
+        let (_, path) = uri.split_once("/ipfs/")?;
+        path
+    } else if uri.starts_with("Qm") || uri.starts_with("baf") {
+        uri
+    } else {
+        return None;
+    };
+
+    let cid = path.split('/').next().unwrap_or_default();
+    (!cid.is_empty() && cid.chars().all(|c| c.is_ascii_alphanumeric())).then(|| path.to_owned())
+}
+
+// A string or an array of strings to be concatenated
+fn chunked_string(value: Option<JsonValue>, field: &str) -> Result<Option<String>, ParseError> {
+    match value {
+        None => Ok(None),
+        Some(JsonValue::String(text)) => Ok(Some(text)),
+        Some(JsonValue::Array(chunks)) => chunks
+            .iter()
+            .map(|chunk| chunk.as_str().ok_or_else(|| not_a_string(field)))
+            .collect::<Result<String, _>>()
+            .map(Some),
+        Some(_) => Err(not_a_string(field)),
+    }
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("cip-25 metadata", reason)
+}
+
+fn missing(field: &str) -> ParseError {
+    error(format!("missing '{}'", field))
+}
+
+fn not_a_string(field: &str) -> ParseError {
+    error(format!("'{}' is not a string nor an array of strings", field))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const POLICY_ID: &str = "d5e6bf0500378d4f0da4e8dde6becec7621cd8cbf5cbb9b87013d4cc";
+
+    #[test]
+    fn test_parse_metadata() {
+        let json = json!({
+            "name": "SpaceBud #1507",
+            "image": ["ipfs://ipfs/QmUn3W8MwdpLCDfFTtMnw1rtKBjzrYHymtLSBAhB3mpK2z"],
+            "description": ["A bud ", "in space"],
+            "files": [{
+                "name": "Video",
+                "mediaType": "video/mp4",
+                "src": ["https://ipfs.io/ipfs/", "QmWfVAMLn5cqT5DMRDW2uAxzeuv5mNLoYmKeAgpcwczrNR/a.mp4"],
+                "duration": 12,
+            }],
+            "traits": ["Star Suit", "Sun Glasses"],
+            "type": "Alien",
+        });
+        let metadata = Cip25Metadata::from_json(&json).unwrap();
+
+        assert_eq!(metadata.description.as_deref(), Some("A bud in space"));
+        assert_eq!(metadata.media_type, None);
+        assert_eq!(
+            metadata.image_ipfs_path().unwrap(),
+            "QmUn3W8MwdpLCDfFTtMnw1rtKBjzrYHymtLSBAhB3mpK2z"
+        );
+        assert_eq!(
+            metadata.files[0].ipfs_path().unwrap(),
+            "QmWfVAMLn5cqT5DMRDW2uAxzeuv5mNLoYmKeAgpcwczrNR/a.mp4"
+        );
+        assert_eq!(metadata.files[0].attributes["duration"], 12);
+        assert_eq!(metadata.attributes["type"], "Alien");
+        assert!(!metadata.attributes.contains_key("name"));
+
+        assert!(Cip25Metadata::from_json(&json!({ "name": "No image" })).is_err());
+        assert!(Cip25Metadata::from_json(&json!({ "name": 1, "image": "ipfs://Qm" })).is_err());
+    }
+
+    #[test]
+    fn test_label_721_versions() {
+        let asset = AssetId::new(POLICY_ID, "537061636542756431353037").unwrap();
+        let metadata = json!({ "name": "SpaceBud #1507", "image": "ipfs://QmUn3W" });
+
+        let v1 = json!({ POLICY_ID: { "SpaceBud1507": metadata } });
+        let v2 = json!({ POLICY_ID: { "537061636542756431353037": metadata }, "version": 2 });
+
+        for label in [v1, v2] {
+            let parsed = Cip25Metadata::from_label_721(&label, &asset).unwrap();
+            assert_eq!(parsed.name, "SpaceBud #1507");
+        }
+
+        let other = AssetId::new(POLICY_ID, "").unwrap();
+        assert!(Cip25Metadata::from_label_721(&json!({ POLICY_ID: {} }), &other).is_err());
+    }
+
+    #[test]
+    fn test_ipfs_path() {
+        let cid = "QmUn3W8MwdpLCDfFTtMnw1rtKBjzrYHymtLSBAhB3mpK2z";
+        assert_eq!(ipfs_path(&format!("ipfs://{}", cid)).unwrap(), cid);
+        assert_eq!(ipfs_path(cid).unwrap(), cid);
+        assert_eq!(
+            ipfs_path(&format!("https://ipfs.blockfrost.dev/ipfs/{}/1", cid)).unwrap(),
+            format!("{}/1", cid)
+        );
+        assert_eq!(ipfs_path("data:image/png;base64,iVBORw0KGgo="), None);
+        assert_eq!(ipfs_path("https://example.com/image.png"), None);
+        assert_eq!(ipfs_path("ipfs://"), None);
+    }
+}
@@ -0,0 +1,195 @@
+//! Typed [CIP-68] token metadata.
+//!
+//! The metadata of a CIP-68 token lives in the inline datum of the UTXO holding its reference NFT
+//! (label 100), not in [`AssetDetails::onchain_metadata`](crate::AssetDetails::onchain_metadata).
+//! [`BlockFrostApi::cip68_metadata`] finds that UTXO and decodes the datum.
+//!
+//! [CIP-68]: https://cips.cardano.org/cips/cip68/
+
+use std::collections::BTreeMap;
+
+use crate::{plutus::PlutusData, AssetId, AssetLabel, BlockFrostApi, ParseError};
+
+/// Metadata of a CIP-68 token, decoded from the datum of its reference NFT.
+///
+/// The datum is `Constr 0 [metadata, version, extra]`, common metadata properties have accessors,
+/// e.g. [`name`](Cip68Metadata::name), and every property is in
+/// [`properties`](Cip68Metadata::properties).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cip68Metadata {
+    /// Label of the user token: [`AssetLabel::Nft`], [`AssetLabel::Ft`] or [`AssetLabel::Rft`].
+    pub label: AssetLabel,
+    /// Every metadata property, keyed by its UTF-8 name.
+    pub properties: BTreeMap<String, PlutusData>,
+    /// Version of the metadata standard.
+    pub version: u64,
+    /// Custom data defined by the token, `None` for datums without the field.
+    pub extra: Option<PlutusData>,
+}
+
+impl Cip68Metadata {
+    /// Decode from the datum of a reference NFT.
+    pub fn from_datum(label: AssetLabel, datum: &PlutusData) -> Result<Self, ParseError> {
+        let fields = match datum.as_constr() {
+            Some((0, fields)) if fields.len() == 2 || fields.len() == 3 => fields,
+            _ => return Err(error("datum is not a constructor 0 with 2 or 3 fields")),
+        };
+
+        let entries = fields[0].as_map().ok_or_else(|| error("metadata is not a map"))?;
+        let properties = entries
+            .iter()
+            .map(|(key, value)| match key.as_utf8() {
+                Some(key) => Ok((key.to_owned(), value.clone())),
+                None => Err(error("metadata key is not UTF-8 bytes")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let version = fields[1]
+            .as_integer()
+            .and_then(|version| u64::try_from(version).ok())
+            .ok_or_else(|| error("version is not a natural number"))?;
+
+        Ok(Self { label, properties, version, extra: fields.get(2).cloned() })
+    }
+
+    /// A text property, stored as UTF-8 bytes or as a list of UTF-8 chunks.
+    pub fn text(&self, key: &str) -> Option<String> {
+        match self.properties.get(key)? {
+            PlutusData::List(chunks) => chunks.iter().map(|chunk| chunk.as_utf8()).collect(),
+            value => value.as_utf8().map(str::to_owned),
+        }
+    }
+
+    /// Name of the token.
+    pub fn name(&self) -> Option<String> {
+        self.text("name")
+    }
+
+    /// URI of the image, see [`cip25::ipfs_path`](crate::cip25::ipfs_path) for IPFS URIs.
+    pub fn image(&self) -> Option<String> {
+        self.text("image")
+    }
+
+    /// MIME type of the image.
+    pub fn media_type(&self) -> Option<String> {
+        self.text("mediaType")
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.text("description")
+    }
+
+    /// Ticker of fungible tokens.
+    pub fn ticker(&self) -> Option<String> {
+        self.text("ticker")
+    }
+
+    /// Website of fungible tokens.
+    pub fn url(&self) -> Option<String> {
+        self.text("url")
+    }
+
+    /// Logo URI of fungible tokens.
+    pub fn logo(&self) -> Option<String> {
+        self.text("logo")
+    }
+
+    /// Number of decimal places of fungible tokens.
+    pub fn decimals(&self) -> Option<u64> {
+        self.properties.get("decimals")?.as_integer()?.try_into().ok()
+    }
+}
+
+impl BlockFrostApi {
+    /// Fetch the CIP-68 metadata of a user token (label 222, 333 or 444).
+    ///
+    /// Finds the address holding the reference NFT with [`assets_addresses`], then its UTXO with
+    /// [`addresses_utxos_asset`], and decodes the inline datum.
+    ///
+    /// Returns `None` if the reference NFT doesn't exist or its UTXO has no inline datum.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// let unit = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7000de1404e7574";
+    /// let asset = unit.parse().expect("valid asset unit");
+    ///
+    /// if let Some(metadata) = api.cip68_metadata(&asset).await? {
+    ///     println!("{:?} (version {})", metadata.name(), metadata.version);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`assets_addresses`]: BlockFrostApi::assets_addresses
+    /// [`addresses_utxos_asset`]: BlockFrostApi::addresses_utxos_asset
+    pub async fn cip68_metadata(&self, asset: &AssetId) -> crate::Result<Option<Cip68Metadata>> {
+        let label = match asset.label() {
+            Some(label @ (AssetLabel::Nft | AssetLabel::Ft | AssetLabel::Rft)) => label,
+            _ => return Err(error(format!("'{}' is not a CIP-68 user token", asset)).into()),
+        };
+        let reference = asset.with_label(AssetLabel::ReferenceNft)?.to_string();
+
+        let holders = match self.assets_addresses(&reference).await {
+            Err(error) if error.is_not_found() => return Ok(None),
+            holders => holders?,
+        };
+        let holder = match holders.first() {
+            Some(holder) => holder,
+            None => return Ok(None),
+        };
+
+        let utxos = match self.addresses_utxos_asset(&holder.address, &reference).await {
+            Err(error) if error.is_not_found() => return Ok(None),
+            utxos => utxos?,
+        };
+        let datum = match utxos.iter().find_map(|utxo| utxo.inline_datum.as_deref()) {
+            Some(datum) => PlutusData::from_cbor_hex(datum)?,
+            None => return Ok(None),
+        };
+
+        Ok(Some(Cip68Metadata::from_datum(label, &datum)?))
+    }
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("cip-68 metadata", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nft_metadata() {
+        // Constr 0 [{ "name": "Nut", "image": ["ipfs://", "QmAb"] }, 1, Constr 0 []]
+        let datum = PlutusData::from_cbor_hex(
+            "d8799fa2446e616d65434e757445696d6167658247697066733a2f2f44516d416201d87980ff",
+        )
+        .unwrap();
+        let metadata = Cip68Metadata::from_datum(AssetLabel::Nft, &datum).unwrap();
+
+        assert_eq!(metadata.name().unwrap(), "Nut");
+        assert_eq!(metadata.image().unwrap(), "ipfs://QmAb");
+        assert_eq!(metadata.description(), None);
+        assert_eq!(metadata.version, 1);
+        assert_eq!(metadata.extra, Some(PlutusData::Constr { constructor: 0, fields: vec![] }));
+    }
+
+    #[test]
+    fn test_fungible_token_metadata() {
+        // Constr 0 [{ "ticker": "NUT", "decimals": 6 }, 1], without extra
+        let datum =
+            PlutusData::from_cbor_hex("d8799fa2467469636b6572434e555448646563696d616c730601ff")
+                .unwrap();
+        let metadata = Cip68Metadata::from_datum(AssetLabel::Ft, &datum).unwrap();
+
+        assert_eq!(metadata.ticker().unwrap(), "NUT");
+        assert_eq!(metadata.decimals(), Some(6));
+        assert_eq!(metadata.extra, None);
+
+        let not_constr = PlutusData::List(vec![]);
+        assert!(Cip68Metadata::from_datum(AssetLabel::Ft, &not_constr).is_err());
+    }
+}
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Error as SerdeJsonError;
 use toml::de::Error as SerdeTomlError;
 
-use crate::utils;
+use crate::{utils, TxHash};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -23,6 +23,16 @@ pub enum Error {
     Io(IoError),
     Toml { path: PathBuf, reason: SerdeTomlError },
     Response { url: String, reason: ResponseError },
+    Parse(ParseError),
+    SubmittedHashMismatch { submitted: TxHash, returned: String },
+    RequestLimit { limit: u32 },
+}
+
+impl Error {
+    // Whether the API answered with 404, e.g. for an asset that doesn't exist
+    pub(crate) fn is_not_found(&self) -> bool {
+        matches!(self, Error::Response { reason, .. } if reason.status_code == 404)
+    }
 }
 
 impl fmt::Display for Error {
@@ -50,6 +60,15 @@ impl fmt::Display for Error {
                 write!(f, "  url: {}\n", url)?;
                 reason.fmt(f)
             }
+            Error::Parse(reason) => write!(f, "parse error: {}.", reason),
+            Error::SubmittedHashMismatch { submitted, returned } => {
+                write!(f, "submitted hash mismatch:\n")?;
+                write!(f, "  the transaction was submitted, its hash is {}\n", submitted)?;
+                write!(f, "  returned hash: {}", returned)
+            }
+            Error::RequestLimit { limit } => {
+                write!(f, "request limit: gave up after {} requests.", limit)
+            }
         }
     }
 }
@@ -62,11 +81,14 @@ impl error::Error for Error {
             Error::Io(source) => Some(source),
             Error::Toml { reason, .. } => Some(reason),
             Error::Response { reason, .. } => Some(reason),
+            Error::Parse(reason) => Some(reason),
+            Error::SubmittedHashMismatch { .. } => None,
+            Error::RequestLimit { .. } => None,
         }
     }
 }
 
-///
+/// Error body returned by the API when a request is not successful.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ResponseError {
     pub status_code: u16,
@@ -84,12 +106,41 @@ impl fmt::Display for ResponseError {
 
 impl error::Error for ResponseError {}
 
+/// Error for data that could not be parsed or validated locally, without making any request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Name of what was being parsed, e.g. `"asset id"`.
+    pub kind: &'static str,
+    /// Why the input is invalid.
+    pub reason: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: &'static str, reason: impl ToString) -> Self {
+        Self { kind, reason: reason.to_string() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid {}: {}", self.kind, self.reason)
+    }
+}
+
+impl error::Error for ParseError {}
+
 impl From<IoError> for Error {
     fn from(source: IoError) -> Self {
         Error::Io(source)
     }
 }
 
+impl From<ParseError> for Error {
+    fn from(source: ParseError) -> Self {
+        Error::Parse(source)
+    }
+}
+
 // Parsing the error response is tricky, it's necessary to check if the json body is
 // malformed, if so, we will catch an error trying to get the cause to another error
 //
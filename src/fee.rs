@@ -0,0 +1,368 @@
+//! Minimum fee of transactions, computed locally from the protocol parameters.
+//!
+//! See [`FeeEstimator`].
+
+use std::{fmt, ops::Add, str::FromStr};
+
+use crate::{EpochParameters, Lovelace, ParseError};
+
+/// Size of the reference script tiers, each tier costs more per byte than the previous one.
+pub const REFERENCE_SCRIPT_TIER_SIZE: u64 = 25_600;
+
+// Multiplier of the cost per byte between tiers, 1.2
+const REFERENCE_SCRIPT_TIER_MULTIPLIER: Rational = Rational { numerator: 6, denominator: 5 };
+
+/// Exact non-negative fraction, used for the prices of the protocol parameters.
+///
+/// The API returns prices as JSON numbers, they are read from their shortest decimal form, so
+/// `0.0577` is exactly `577/10000`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: u128,
+    denominator: u128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numerator: 0, denominator: 1 };
+
+    /// Create a reduced fraction, `None` if the denominator is zero.
+    pub fn new(numerator: u128, denominator: u128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator);
+        Some(Self { numerator: numerator / divisor, denominator: denominator / divisor })
+    }
+
+    /// Read a price from its shortest decimal form, `None` for negative or non-finite values.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+        value.to_string().parse().ok()
+    }
+
+    pub fn numerator(&self) -> u128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u128 {
+        self.denominator
+    }
+
+    /// Checked multiplication, `None` on overflow.
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        let left = gcd(self.numerator, other.denominator).max(1);
+        let right = gcd(other.numerator, self.denominator).max(1);
+        let numerator = (self.numerator / left).checked_mul(other.numerator / right)?;
+        let denominator = (self.denominator / right).checked_mul(other.denominator / left)?;
+        Rational::new(numerator, denominator)
+    }
+
+    /// Checked addition, `None` on overflow.
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let divisor = gcd(self.denominator, other.denominator);
+        let denominator = (self.denominator / divisor).checked_mul(other.denominator)?;
+        let numerator = (self.numerator.checked_mul(other.denominator / divisor)?)
+            .checked_add(other.numerator.checked_mul(self.denominator / divisor)?)?;
+        Rational::new(numerator, denominator)
+    }
+
+    pub fn floor(&self) -> u128 {
+        self.numerator / self.denominator
+    }
+
+    pub fn ceil(&self) -> u128 {
+        let floor = self.floor();
+        if floor * self.denominator == self.numerator {
+            floor
+        } else {
+            floor + 1
+        }
+    }
+}
+
+impl From<u64> for Rational {
+    fn from(integer: u64) -> Self {
+        Self { numerator: integer.into(), denominator: 1 }
+    }
+}
+
+/// Parses decimals such as `0.0577`, and fractions such as `577/10000`.
+impl FromStr for Rational {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error =
+            || ParseError::new("rational", format!("'{}' is not a non-negative number", text));
+        let digits = |text: &str| -> Result<u128, ParseError> {
+            if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(error());
+            }
+            text.parse().map_err(|_| error())
+        };
+
+        let rational = if let Some((numerator, denominator)) = text.split_once('/') {
+            Rational::new(digits(numerator)?, digits(denominator)?)
+        } else if let Some((integer, decimals)) = text.split_once('.') {
+            let scale = 10u128.checked_pow(decimals.len() as u32).ok_or_else(error)?;
+            let numerator = digits(integer)?
+                .checked_mul(scale)
+                .and_then(|integer| integer.checked_add(digits(decimals).ok()?));
+            Rational::new(numerator.ok_or_else(error)?, scale)
+        } else {
+            Rational::new(digits(text)?, 1)
+        };
+        rational.ok_or_else(error)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// Execution units used by a script, summed over the redeemers of a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExUnits {
+    pub mem: u64,
+    pub steps: u64,
+}
+
+impl Add for ExUnits {
+    type Output = ExUnits;
+
+    fn add(self, other: ExUnits) -> ExUnits {
+        ExUnits {
+            mem: self.mem.saturating_add(other.mem),
+            steps: self.steps.saturating_add(other.steps),
+        }
+    }
+}
+
+/// Computes the minimum fee of a transaction, as the ledger does:
+///
+/// - `min_fee_a * size + min_fee_b` for the transaction size in bytes;
+/// - `price_mem * mem + price_step * steps`, rounded up, for the scripts;
+/// - and, since Conway, a fee for the size of the reference scripts of the inputs, with a cost
+///   per byte multiplied by 1.2 every [`REFERENCE_SCRIPT_TIER_SIZE`] bytes, rounded down.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(api: blockfrost::BlockFrostApi, tx: Vec<u8>) -> blockfrost::Result<()> {
+/// use blockfrost::fee::{ExUnits, FeeEstimator};
+///
+/// let parameters = api.epochs_latest_parameters().await?;
+/// let estimator = FeeEstimator::try_from(&parameters)?;
+///
+/// let ex_units = ExUnits { mem: 1_000_000, steps: 500_000_000 };
+/// let fee = estimator.min_fee_for_tx(&tx, ex_units, 0);
+/// println!("fee: {}", fee);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeEstimator {
+    /// Lovelaces per byte of transaction.
+    pub min_fee_a: u64,
+    /// Constant lovelaces per transaction.
+    pub min_fee_b: u64,
+    /// Lovelaces per unit of memory.
+    pub price_mem: Rational,
+    /// Lovelaces per CPU step.
+    pub price_step: Rational,
+    /// Lovelaces per byte of reference script, in the first tier.
+    pub ref_script_cost_per_byte: Rational,
+}
+
+/// Fails for negative fee factors and for prices that are missing, before the Alonzo and Conway
+/// eras, or not a non-negative number.
+impl TryFrom<&EpochParameters> for FeeEstimator {
+    type Error = ParseError;
+
+    fn try_from(parameters: &EpochParameters) -> Result<Self, Self::Error> {
+        Ok(Self {
+            min_fee_a: fee_factor("min_fee_a", parameters.min_fee_a)?,
+            min_fee_b: fee_factor("min_fee_b", parameters.min_fee_b)?,
+            price_mem: price("price_mem", parameters.price_mem)?,
+            price_step: price("price_step", parameters.price_step)?,
+            ref_script_cost_per_byte: price(
+                "min_fee_ref_script_cost_per_byte",
+                parameters.min_fee_ref_script_cost_per_byte,
+            )?,
+        })
+    }
+}
+
+impl FeeEstimator {
+    /// Minimum fee of a transaction of `tx_size` bytes, running scripts with the total
+    /// `ex_units`, and spending or referencing inputs with `reference_scripts_size` bytes of
+    /// reference scripts.
+    pub fn min_fee(
+        &self,
+        tx_size: u64,
+        ex_units: ExUnits,
+        reference_scripts_size: u64,
+    ) -> Lovelace {
+        let fee = u128::from(self.size_fee(tx_size).0)
+            + u128::from(self.script_fee(ex_units).0)
+            + u128::from(self.reference_scripts_fee(reference_scripts_size).0);
+        Lovelace(saturate(fee))
+    }
+
+    /// [`min_fee`](FeeEstimator::min_fee) for a serialized (CBOR) transaction.
+    ///
+    /// The transaction should include its witnesses, or placeholders of the same size, since they
+    /// are part of the size.
+    pub fn min_fee_for_tx(
+        &self,
+        tx: &[u8],
+        ex_units: ExUnits,
+        reference_scripts_size: u64,
+    ) -> Lovelace {
+        self.min_fee(tx.len() as u64, ex_units, reference_scripts_size)
+    }
+
+    /// Fee for the size of a transaction.
+    pub fn size_fee(&self, tx_size: u64) -> Lovelace {
+        let fee = u128::from(self.min_fee_a) * u128::from(tx_size) + u128::from(self.min_fee_b);
+        Lovelace(saturate(fee))
+    }
+
+    /// Fee for running scripts, rounded up.
+    pub fn script_fee(&self, ex_units: ExUnits) -> Lovelace {
+        let fee = self
+            .price_mem
+            .checked_mul(ex_units.mem.into())
+            .zip(self.price_step.checked_mul(ex_units.steps.into()))
+            .and_then(|(mem, steps)| mem.checked_add(steps));
+        Lovelace(fee.map_or(u64::MAX, |fee| saturate(fee.ceil())))
+    }
+
+    /// Fee for the size of the reference scripts, rounded down.
+    pub fn reference_scripts_fee(&self, reference_scripts_size: u64) -> Lovelace {
+        // Every tier is free, and there may be too many of them to go through
+        if self.ref_script_cost_per_byte == Rational::ZERO {
+            return Lovelace(0);
+        }
+
+        let mut fee = Rational::ZERO;
+        let mut price = self.ref_script_cost_per_byte;
+        let mut remaining = reference_scripts_size;
+
+        loop {
+            let size = remaining.min(REFERENCE_SCRIPT_TIER_SIZE);
+            let tier_fee = price.checked_mul(size.into()).and_then(|tier| fee.checked_add(tier));
+            fee = match tier_fee {
+                Some(fee) => fee,
+                None => return Lovelace(u64::MAX),
+            };
+            remaining -= size;
+            if remaining == 0 {
+                return Lovelace(saturate(fee.floor()));
+            }
+            price = match price.checked_mul(REFERENCE_SCRIPT_TIER_MULTIPLIER) {
+                Some(price) => price,
+                None => return Lovelace(u64::MAX),
+            };
+        }
+    }
+}
+
+fn saturate(value: u128) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn fee_factor(name: &str, value: i64) -> Result<u64, ParseError> {
+    u64::try_from(value).map_err(|_| error(format!("{} {} is negative", name, value)))
+}
+
+fn price(name: &str, value: Option<f64>) -> Result<Rational, ParseError> {
+    let value = value.ok_or_else(|| error(format!("no {} in this epoch", name)))?;
+    Rational::from_f64(value)
+        .ok_or_else(|| error(format!("{} {} is not a non-negative number", name, value)))
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("fee parameters", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mainnet parameters since epoch 507
+    fn estimator() -> FeeEstimator {
+        FeeEstimator {
+            min_fee_a: 44,
+            min_fee_b: 155381,
+            price_mem: Rational::from_f64(0.0577).unwrap(),
+            price_step: Rational::from_f64(0.0000721).unwrap(),
+            ref_script_cost_per_byte: Rational::from_f64(15.0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_rational_parsing() {
+        assert_eq!(Rational::from_f64(0.0577), Rational::new(577, 10000));
+        assert_eq!(Rational::from_f64(0.0000721), Rational::new(721, 10_000_000));
+        assert_eq!("1.5".parse::<Rational>().unwrap(), Rational::new(3, 2).unwrap());
+        assert_eq!("3/6".parse::<Rational>().unwrap(), Rational::new(1, 2).unwrap());
+        assert!("-1".parse::<Rational>().is_err());
+        assert!("1/0".parse::<Rational>().is_err());
+        assert!("1e-3".parse::<Rational>().is_err());
+        assert_eq!(Rational::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!(price("price_mem", Some(0.0577)), Ok(Rational::new(577, 10000).unwrap()));
+        assert!(price("price_mem", None).is_err());
+        assert!(price("price_mem", Some(-1.0)).is_err());
+        assert!(price("price_mem", Some(f64::INFINITY)).is_err());
+        assert_eq!(fee_factor("min_fee_a", 44), Ok(44));
+        assert!(fee_factor("min_fee_a", -1).is_err());
+    }
+
+    #[test]
+    fn test_min_fee() {
+        let estimator = estimator();
+        assert_eq!(estimator.size_fee(300), Lovelace(168581));
+
+        let ex_units = ExUnits { mem: 1_000_000, steps: 500_000_000 };
+        assert_eq!(estimator.script_fee(ex_units), Lovelace(93750));
+
+        // 57.7577 + 0.0000721, rounded up
+        assert_eq!(estimator.script_fee(ExUnits { mem: 1001, steps: 1 }), Lovelace(58));
+
+        assert_eq!(estimator.min_fee(300, ex_units, 0), Lovelace(168581 + 93750));
+        assert_eq!(estimator.min_fee_for_tx(&[0; 300], ExUnits::default(), 0), Lovelace(168581));
+    }
+
+    #[test]
+    fn test_reference_scripts_fee() {
+        let estimator = estimator();
+        assert_eq!(estimator.reference_scripts_fee(0), Lovelace(0));
+        assert_eq!(estimator.reference_scripts_fee(25_600), Lovelace(384_000));
+        // 25600 * 15 + 4400 * 18
+        assert_eq!(estimator.reference_scripts_fee(30_000), Lovelace(463_200));
+        // 25600 * 15 + 25600 * 18 + 8800 * 21.6
+        assert_eq!(estimator.reference_scripts_fee(60_000), Lovelace(1_034_880));
+        // 500.5, rounded down
+        let half =
+            FeeEstimator { ref_script_cost_per_byte: Rational::new(1, 2).unwrap(), ..estimator };
+        assert_eq!(half.reference_scripts_fee(1001), Lovelace(500));
+
+        let free = FeeEstimator { ref_script_cost_per_byte: Rational::ZERO, ..estimator };
+        assert_eq!(free.reference_scripts_fee(u64::MAX), Lovelace(0));
+    }
+}
@@ -0,0 +1,263 @@
+//! Validated identifiers: hashes, pool IDs, addresses and asset fingerprints.
+//!
+//! Every type wraps a `String` that was validated locally (hex length, bech32 prefix and
+//! checksum), so typos are caught before making a request.
+//!
+//! All of them implement [`Deref<Target = str>`](std::ops::Deref) and [`AsRef<str>`], so they can
+//! be passed to the endpoint methods directly:
+//!
+//! ```
+//! use blockfrost::{BlockFrostApi, TxHash};
+//!
+//! async fn fetch(api: &BlockFrostApi) -> blockfrost::Result<()> {
+//!     let hash: TxHash = "1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477"
+//!         .parse()
+//!         .expect("valid transaction hash");
+//!
+//!     let transaction = api.transaction_by_hash(&hash).await?;
+//!     assert_eq!(transaction.hash, hash);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! These are re-exported at the root of the crate, except for [`Address`], which would clash with
+//! the [`Address`](crate::Address) response type.
+
+use std::{fmt, ops::Deref, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    address::{AddressKind, DecodedAddress},
+    utils::{decode_bech32, decode_hex, encode_bech32, encode_hex, is_hex},
+    ParseError,
+};
+
+// Defines a validated string identifier.
+macro_rules! identifiers {
+  ($($(#[doc = $doc:expr])* $name:ident, $kind:expr, $validate:expr;)*) => {
+    $(
+      $(#[doc = $doc])*
+      #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+      #[serde(try_from = "String", into = "String")]
+      pub struct $name(String);
+
+      impl $name {
+        /// Name of this identifier in error messages.
+        const KIND: &'static str = $kind;
+
+        /// The identifier as a string slice.
+        pub fn as_str(&self) -> &str {
+          &self.0
+        }
+      }
+
+      impl TryFrom<String> for $name {
+        type Error = ParseError;
+
+        fn try_from(text: String) -> Result<Self, Self::Error> {
+          let validate: fn(&str) -> Result<(), String> = $validate;
+          match validate(&text) {
+            Ok(()) => Ok(Self(text)),
+            Err(reason) => Err(ParseError::new(Self::KIND, format!("'{}' {}", text, reason))),
+          }
+        }
+      }
+
+      impl FromStr for $name {
+        type Err = ParseError;
+
+        fn from_str(text: &str) -> Result<Self, Self::Err> {
+          Self::try_from(text.to_owned())
+        }
+      }
+
+      impl From<$name> for String {
+        fn from(identifier: $name) -> Self {
+          identifier.0
+        }
+      }
+
+      impl Deref for $name {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+          &self.0
+        }
+      }
+
+      impl AsRef<str> for $name {
+        fn as_ref(&self) -> &str {
+          &self.0
+        }
+      }
+
+      impl fmt::Display for $name {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          f.write_str(&self.0)
+        }
+      }
+
+      impl PartialEq<str> for $name {
+        fn eq(&self, other: &str) -> bool {
+          self.0 == other
+        }
+      }
+
+      impl PartialEq<&str> for $name {
+        fn eq(&self, other: &&str) -> bool {
+          self.0 == *other
+        }
+      }
+
+      impl PartialEq<String> for $name {
+        fn eq(&self, other: &String) -> bool {
+          &self.0 == other
+        }
+      }
+    )*
+  };
+}
+
+identifiers! {
+    /// Hash of a transaction, 32 hex-encoded bytes.
+    TxHash, "transaction hash", |text| validate_hex(text, 32);
+
+    /// Hash of a block, 32 hex-encoded bytes.
+    BlockHash, "block hash", |text| validate_hex(text, 32);
+
+    /// Hash of a datum, 32 hex-encoded bytes.
+    DatumHash, "datum hash", |text| validate_hex(text, 32);
+
+    /// Policy ID of a native asset, 28 hex-encoded bytes.
+    PolicyId, "policy id", |text| validate_hex(text, 28);
+
+    /// Bech32 ID of a stake pool, e.g. `pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy`.
+    PoolId, "pool id", |text| validate_bech32(text, &["pool"], 28).map(drop);
+
+    /// Bech32 stake (reward) address, e.g.
+    /// `stake1u9ylzsgxaa6xctf4juup682ar3juj85n8tx3hthnljg47zctvm3rc`.
+    StakeAddress, "stake address", validate_stake_address;
+
+    /// Payment address, either a Shelley bech32 address (`addr` or `addr_test`) or a Byron
+    /// base58 address.
+    Address, "address", validate_address;
+
+    /// CIP-14 asset fingerprint, e.g. `asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3`.
+    AssetFingerprint, "asset fingerprint", |text| validate_bech32(text, &["asset"], 20).map(drop);
+}
+
+fn validate_hex(text: &str, bytes: usize) -> Result<(), String> {
+    if text.len() != bytes * 2 || !is_hex(text) {
+        return Err(format!("is not {} hex-encoded bytes", bytes));
+    }
+    Ok(())
+}
+
+fn validate_bech32(text: &str, prefixes: &[&str], bytes: usize) -> Result<Vec<u8>, String> {
+    let (hrp, data) = decode_bech32(text).map_err(|reason| format!("is not bech32: {}", reason))?;
+
+    if !prefixes.contains(&hrp.as_str()) {
+        return Err(format!("has prefix '{}', expected one of {:?}", hrp, prefixes));
+    }
+    if data.len() != bytes {
+        return Err(format!("has {} bytes of data, expected {}", data.len(), bytes));
+    }
+    Ok(data)
+}
+
+fn validate_stake_address(text: &str) -> Result<(), String> {
+    match DecodedAddress::parse(text) {
+        Ok(address) if address.kind == AddressKind::Reward => Ok(()),
+        Ok(_) => Err("is not a reward address".to_owned()),
+        Err(error) => Err(strip_quoted_text(error.reason, text)),
+    }
+}
+
+fn validate_address(text: &str) -> Result<(), String> {
+    match DecodedAddress::parse(text) {
+        Ok(address) if address.kind != AddressKind::Reward => Ok(()),
+        Ok(_) => Err("is not a payment address".to_owned()),
+        Err(error) => Err(strip_quoted_text(error.reason, text)),
+    }
+}
+
+// The address decoder quotes the input too, avoid repeating it
+fn strip_quoted_text(reason: String, text: &str) -> String {
+    let quoted = format!("'{}' ", text);
+    reason.strip_prefix(&quoted).map(str::to_owned).unwrap_or(reason)
+}
+
+impl PoolId {
+    /// Pool ID from the hex-encoded hash of the pool's cold verification key, as used on chain.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseError> {
+        let bytes = decode_hex(hex).filter(|bytes| bytes.len() == 28).ok_or_else(|| {
+            ParseError::new(Self::KIND, format!("'{}' is not 28 hex-encoded bytes", hex))
+        })?;
+        Ok(Self(encode_bech32("pool", &bytes)))
+    }
+
+    /// Hex-encoded hash of the pool's cold verification key.
+    pub fn to_hex(&self) -> String {
+        // Safety: validated on construction
+        let (_, bytes) = decode_bech32(&self.0).unwrap();
+        encode_hex(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_identifiers() {
+        let hash = "1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477";
+        assert_eq!(hash.parse::<TxHash>().unwrap(), hash);
+        assert!(hash.parse::<BlockHash>().is_ok());
+        assert!(hash[1..].parse::<TxHash>().is_err());
+        assert!(hash.replace('e', "g").parse::<TxHash>().is_err());
+        assert!(hash.parse::<PolicyId>().is_err());
+        assert!(hash[..56].parse::<PolicyId>().is_ok());
+    }
+
+    #[test]
+    fn test_bech32_identifiers() {
+        let pool = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+        assert!(pool.parse::<PoolId>().is_ok());
+        assert!(pool.replace("q3lk", "q3lj").parse::<PoolId>().is_err());
+
+        let hex = "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735";
+        assert_eq!(pool.parse::<PoolId>().unwrap().to_hex(), hex);
+        assert_eq!(PoolId::from_hex(hex).unwrap(), pool);
+        assert!(PoolId::from_hex(&hex[2..]).is_err());
+
+        let fingerprint = "asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3";
+        assert!(fingerprint.parse::<AssetFingerprint>().is_ok());
+        assert!(fingerprint.parse::<PoolId>().is_err());
+
+        let stake = "stake1u9ylzsgxaa6xctf4juup682ar3juj85n8tx3hthnljg47zctvm3rc";
+        assert!(stake.parse::<StakeAddress>().is_ok());
+        assert!(stake.parse::<Address>().is_err());
+    }
+
+    #[test]
+    fn test_addresses() {
+        let shelley = "addr1qxqs59lphg8g6qndelq8xwqn60ag3aeyfcp33c2kdp46a09re5df3pzwwmyq946axfcejy5n4x0y99wqpgtp2gd0k09qsgy6pz";
+        assert!(shelley.parse::<Address>().is_ok());
+        assert!(shelley.replace("gy6pz", "gy6py").parse::<Address>().is_err());
+
+        let byron = "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi";
+        assert!(byron.parse::<Address>().is_ok());
+        assert!(byron.replace("MAi", "MAj").parse::<Address>().is_err());
+
+        let daedalus = "DdzFFzCqrhsw3prhfMFDNFowbzUku3QmrMwarfjUbWXRisodn97R436SHc1rimp4MhPNmbdYb1aTdqtGSJixMVMi5MkArDQJ6Sc1n3Ez";
+        assert!(daedalus.parse::<Address>().is_ok());
+    }
+
+    #[test]
+    fn test_serde_validates() {
+        let json = r#""1e043f100dce12d107f679685acd2fc0610e10f72a92d412794c9773d11d8477""#;
+        assert!(serde_json::from_str::<TxHash>(json).is_ok());
+        assert!(serde_json::from_str::<TxHash>(r#""string""#).is_err());
+    }
+}
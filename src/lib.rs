@@ -3,26 +3,46 @@
 )]
 #![doc = include_str!("../README.md")]
 
-// Internal macros for testing and implementing endpoints
+// Internal macros for testing and implementing endpoints, and the exported `custom_endpoints!`
 #[macro_use]
 mod macros;
 
 mod api;
+mod cbor;
 mod ipfs;
+mod meta;
 mod request;
 mod settings;
 mod url;
 mod utils;
 
+pub mod address;
+pub mod cip20;
+pub mod cip25;
+pub mod cip68;
 pub mod error;
+pub mod fee;
+pub mod ids;
 pub mod load;
+pub mod min_utxo;
+pub mod parameters;
+pub mod plutus;
+pub mod script;
+pub mod submit;
+pub mod time;
+pub mod transaction;
+pub mod tx_metadata;
 pub mod types;
+pub mod value;
 
 pub use api::*;
 pub use error::*;
+pub use ids::{AssetFingerprint, BlockHash, DatumHash, PolicyId, PoolId, StakeAddress, TxHash};
 pub use ipfs::IpfsApi;
+pub use meta::WithMeta;
 pub use settings::*;
 pub use types::*;
+pub use value::*;
 
 /// [`Lister`] stream.
 ///
@@ -61,6 +81,13 @@ pub mod stream {
     pub use futures::stream::{Stream, StreamExt};
 }
 
+// Used by exported macros, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use futures::future::BoxFuture;
+    pub use paste;
+}
+
 /// The URL of the [BlockFrost API](https://docs.blockfrost.io) for the Cardano mainnet.
 pub const CARDANO_MAINNET_NETWORK: &str = "https://cardano-mainnet.blockfrost.io/api/v0";
 /// The URL of the [BlockFrost API](https://docs.blockfrost.io) for the Cardano preprod net.
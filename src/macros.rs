@@ -31,7 +31,21 @@ macro_rules! def_endpoint {
         // A cool property here is that the parameters defined in the macro will get checked against the route provided
         let route = format!($route $(, $param = $param)*);
         // Make a GET request!
-        self.get_from_endpoint(&route)
+        self.get(&route)
+    }
+
+    // Use paste! to append _with_meta to the name, for the variant that keeps response metadata
+    paste::paste! {
+      #[doc = $doc]
+      #[doc = ""]
+      #[doc = "Returns the response metadata together with the body, see [`WithMeta`](crate::WithMeta)."]
+      pub fn [<$name _with_meta>]<'api>(
+          &'api self
+          $(, $param: $ptype)*
+      ) -> impl std::future::Future<Output = Result<$crate::WithMeta<$ret>>> + Send + 'api {
+          let route = format!($route $(, $param = $param)*);
+          self.get_with_meta(&route)
+      }
     }
   };
 }
@@ -139,3 +153,121 @@ macro_rules! paged_endpoints {
     })*
   };
 }
+
+/// Define an extension trait for [`BlockFrostApi`] with custom endpoints.
+///
+/// Useful when talking to a backend that serves routes that are not part of the official API, the
+/// syntax is the same one used to define every endpoint of this crate, each endpoint gets a
+/// method returning a single object (or page) and an `_all` variant returning a [`Lister`].
+///
+/// Requests use the same headers, retry settings, query parameters and error handling as the
+/// built-in methods, see [`BlockFrostApi::get`] and [`BlockFrostApi::list`].
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::{custom_endpoints, BlockFrostApi};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// pub struct PoolStat {
+///     pub epoch: i64,
+///     pub blocks: i64,
+/// }
+///
+/// custom_endpoints! {
+///     /// Routes served by our self-hosted backend.
+///     pub trait CustomEndpoints {
+///         /// Block statistics of a pool per epoch.
+///         pool_stats(pool_id: &str) -> Vec<PoolStat> => "/custom/pools/{pool_id}/stats";
+///             ("https://example.com/docs#pool-stats"),
+///     }
+/// }
+///
+/// async fn print_stats(api: &BlockFrostApi) -> blockfrost::Result<()> {
+///     let stats = api.pool_stats("pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy").await?;
+///     println!("{:#?}", stats);
+///     Ok(())
+/// }
+/// ```
+///
+/// [`BlockFrostApi`]: crate::BlockFrostApi
+/// [`BlockFrostApi::get`]: crate::BlockFrostApi::get
+/// [`BlockFrostApi::list`]: crate::BlockFrostApi::list
+/// [`Lister`]: crate::stream::Lister
+#[macro_export]
+macro_rules! custom_endpoints {
+  (
+    $(#[$attr:meta])*
+    $vis:vis trait $trait_name:ident {
+      $($(#[doc = $doc:expr])* $name:ident($($param:ident: $ptype:ty$(,)?)*) -> $ret:ty => $route:expr; ($link:tt)$(,)?)*
+    }
+  ) => {
+    $crate::__private::paste::paste! {
+      $(#[$attr])*
+      $vis trait $trait_name {
+        $(
+          #[doc = concat!(
+            $($doc,)*
+            "\n\nEndpoint reference: [`", $route, "`].",
+            "\n\n[`", $route, "`]: ", $link
+          )]
+          fn $name<'api>(
+            &'api self
+            $(, $param: $ptype)*
+          ) -> $crate::__private::BoxFuture<'api, $crate::Result<$ret>>;
+
+          #[doc = concat!(
+            $($doc,)*
+            "\n\nReturns the response metadata together with the body, see `blockfrost::WithMeta`.",
+            "\n\nEndpoint reference: [`", $route, "`].",
+            "\n\n[`", $route, "`]: ", $link
+          )]
+          fn [<$name _with_meta>]<'api>(
+            &'api self
+            $(, $param: $ptype)*
+          ) -> $crate::__private::BoxFuture<'api, $crate::Result<$crate::WithMeta<$ret>>>;
+
+          #[doc = concat!(
+            $($doc,)*
+            "\n\nResults are paginated.",
+            "\n\nEndpoint reference: [`", $route, "`].",
+            "\n\n[`", $route, "`]: ", $link
+          )]
+          fn [<$name _all>]<'api>(
+            &'api self
+            $(, $param: $ptype)*
+          ) -> $crate::stream::Lister<'api, $ret>;
+        )*
+      }
+
+      impl $trait_name for $crate::BlockFrostApi {
+        $(
+          fn $name<'api>(
+            &'api self
+            $(, $param: $ptype)*
+          ) -> $crate::__private::BoxFuture<'api, $crate::Result<$ret>> {
+            let route = format!($route $(, $param = $param)*);
+            ::std::boxed::Box::pin($crate::BlockFrostApi::get(self, &route))
+          }
+
+          fn [<$name _with_meta>]<'api>(
+            &'api self
+            $(, $param: $ptype)*
+          ) -> $crate::__private::BoxFuture<'api, $crate::Result<$crate::WithMeta<$ret>>> {
+            let route = format!($route $(, $param = $param)*);
+            ::std::boxed::Box::pin($crate::BlockFrostApi::get_with_meta(self, &route))
+          }
+
+          fn [<$name _all>]<'api>(
+            &'api self
+            $(, $param: $ptype)*
+          ) -> $crate::stream::Lister<'api, $ret> {
+            let endpoint = format!($route $(, $param = $param)*);
+            $crate::BlockFrostApi::list(self, endpoint)
+          }
+        )*
+      }
+    }
+  };
+}
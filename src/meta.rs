@@ -0,0 +1,62 @@
+//! Response metadata returned by the `_with_meta` methods.
+//!
+//! See [`WithMeta`].
+
+use std::time::Duration;
+
+use reqwest::{header::HeaderMap, StatusCode};
+
+/// Parsed response body together with the metadata of the HTTP response.
+///
+/// Returned by [`BlockFrostApi::get_with_meta`] and by the `_with_meta` variant of every endpoint
+/// method, useful for monitoring latency and reading headers that are not part of the body.
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::BlockFrostApi;
+///
+/// async fn tip_latency(api: &BlockFrostApi) -> blockfrost::Result<()> {
+///     let response = api.blocks_latest_with_meta().await?;
+///
+///     println!("block: {}", response.data.hash);
+///     println!("took {:?} after {} retries", response.elapsed, response.retries);
+///     println!("rate limit: {:?}", response.header("ratelimit-remaining"));
+///     Ok(())
+/// }
+/// ```
+///
+/// [`BlockFrostApi::get_with_meta`]: crate::BlockFrostApi::get_with_meta
+#[derive(Debug, Clone)]
+pub struct WithMeta<T> {
+    /// Parsed response body.
+    pub data: T,
+    /// Status code of the response.
+    pub status: StatusCode,
+    /// Headers of the response.
+    pub headers: HeaderMap,
+    /// Final URL of the response, after redirects.
+    pub url: String,
+    /// Amount of retries performed because of rate limiting.
+    pub retries: u64,
+    /// Time elapsed from sending the first request to receiving the whole body.
+    pub elapsed: Duration,
+}
+
+impl<T> WithMeta<T> {
+    /// Get the value of a response header, if present and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Discard the metadata, returning the parsed body.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    /// Transform the parsed body, keeping the metadata.
+    pub fn map<U>(self, function: impl FnOnce(T) -> U) -> WithMeta<U> {
+        let WithMeta { data, status, headers, url, retries, elapsed } = self;
+        WithMeta { data: function(data), status, headers, url, retries, elapsed }
+    }
+}
@@ -0,0 +1,303 @@
+//! Minimum amount of lovelaces that a transaction output must carry, also known as min-UTXO or
+//! min-ADA.
+//!
+//! See [`TxOutput::min_lovelace`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    address::DecodedAddress, cbor::Encoder, utils::decode_hex, AssetId, DatumHash, EpochParameters,
+    Lovelace, ParseError, Value,
+};
+
+// Bytes added to the size of outputs since Babbage, for the transaction input and overhead
+const BABBAGE_OUTPUT_OVERHEAD: u64 = 160;
+
+// Words of the Alonzo size formula
+const ALONZO_ENTRY_SIZE_WITHOUT_VALUE: u64 = 27;
+const ALONZO_COIN_SIZE: u64 = 2;
+const ALONZO_DATUM_HASH_SIZE: u64 = 10;
+const ALONZO_POLICY_ID_SIZE: u64 = 28;
+
+/// Cost of storing outputs, from the protocol parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UtxoCost {
+    /// `coins_per_utxo_size`, lovelaces per byte of output since Babbage.
+    PerByte(u64),
+    /// `coins_per_utxo_word`, lovelaces per 8 bytes word of output in Alonzo.
+    PerWord(u64),
+}
+
+impl UtxoCost {
+    /// Read from the protocol parameters, preferring `coins_per_utxo_size` when it's set.
+    ///
+    /// Fails for epochs before Alonzo, which have neither.
+    pub fn from_parameters(parameters: &EpochParameters) -> Result<Self, ParseError> {
+        let error = |reason| ParseError::new("utxo cost", reason);
+        let parse = |text: &str| {
+            text.parse::<u64>().map_err(|_| error(format!("'{}' is not a lovelace amount", text)))
+        };
+
+        match parameters.coins_per_utxo_size.as_deref().map(parse) {
+            Some(Ok(cost)) if cost > 0 => Ok(UtxoCost::PerByte(cost)),
+            _ => match parameters.coins_per_utxo_word.as_deref() {
+                Some(cost) => parse(cost).map(UtxoCost::PerWord),
+                None => Err(error("no coins per UTXO byte nor word before Alonzo".to_owned())),
+            },
+        }
+    }
+}
+
+/// Datum attached to an output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum OutputDatum {
+    #[default]
+    None,
+    /// Hash of the datum, 32 bytes.
+    Hash(Vec<u8>),
+    /// CBOR of the datum itself, since Babbage.
+    Inline(Vec<u8>),
+}
+
+/// Description of a transaction output, enough to compute its size.
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::{min_utxo::{TxOutput, UtxoCost}, AssetId, Lovelace, Value};
+///
+/// let asset: AssetId = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a76e7574636f696e"
+///     .parse()
+///     .unwrap();
+/// let value = Value::new().with_asset(asset, 100).unwrap();
+///
+/// let output = TxOutput::new("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8", value)
+///     .unwrap();
+///
+/// assert_eq!(output.min_lovelace(UtxoCost::PerByte(4310)), Lovelace(1_034_400));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxOutput {
+    /// Raw bytes of the address.
+    pub address: Vec<u8>,
+    /// Value of the output, only its native assets matter for the minimum.
+    pub value: Value,
+    pub datum: OutputDatum,
+    /// CBOR of the reference script, `[language, script]` as in the ledger.
+    pub script_ref: Option<Vec<u8>>,
+}
+
+impl TxOutput {
+    /// Create an output without datum nor reference script, to a bech32 or Byron address.
+    pub fn new(address: &str, value: Value) -> Result<Self, ParseError> {
+        let address = DecodedAddress::parse(address)?.bytes;
+        Ok(Self { address, value, datum: OutputDatum::None, script_ref: None })
+    }
+
+    /// Decode the address, Byron addresses are the CBOR of their base58 form.
+    pub fn decoded_address(&self) -> Result<DecodedAddress, ParseError> {
+        match self.address.first() {
+            Some(header) if header >> 4 == 0b1000 => {
+                DecodedAddress::parse(&bs58::encode(&self.address).into_string())
+            }
+            _ => DecodedAddress::from_bytes(self.address.clone()),
+        }
+    }
+
+    /// Attach the hash of a datum.
+    pub fn with_datum_hash(mut self, hash: &DatumHash) -> Self {
+        // Safety: validated hex
+        self.datum = OutputDatum::Hash(decode_hex(hash).unwrap_or_default());
+        self
+    }
+
+    /// Attach an inline datum, from its CBOR.
+    pub fn with_inline_datum(mut self, datum: Vec<u8>) -> Self {
+        self.datum = OutputDatum::Inline(datum);
+        self
+    }
+
+    /// Attach a reference script, from its CBOR.
+    pub fn with_script_ref(mut self, script: Vec<u8>) -> Self {
+        self.script_ref = Some(script);
+        self
+    }
+
+    /// The minimum amount of lovelaces this output must carry.
+    ///
+    /// Since Babbage, it's `(160 + output size in bytes) * coins_per_utxo_size`. The size depends
+    /// on the encoding of the amount itself, the result is the smallest amount that covers the
+    /// size of an output carrying it. Outputs without inline datum nor reference script are
+    /// measured in the legacy array format, like most wallets serialize them, the others in the
+    /// map format.
+    ///
+    /// In Alonzo, it's `(27 + value size + 10 with a datum hash) * coins_per_utxo_word`, where
+    /// sizes are in words, inline datums and reference scripts didn't exist yet and are ignored.
+    pub fn min_lovelace(&self, cost: UtxoCost) -> Lovelace {
+        match cost {
+            UtxoCost::PerByte(cost) => {
+                let min_for = |coin| {
+                    let size = self.to_cbor(coin).len() as u64;
+                    (BABBAGE_OUTPUT_OVERHEAD + size).saturating_mul(cost)
+                };
+                // The encoded size grows with the amount, stops when an amount covers itself
+                let mut coin = min_for(0);
+                loop {
+                    let min = min_for(coin);
+                    if min == coin {
+                        return Lovelace(coin);
+                    }
+                    coin = min;
+                }
+            }
+            UtxoCost::PerWord(cost) => {
+                let datum = match self.datum {
+                    OutputDatum::Hash(_) => ALONZO_DATUM_HASH_SIZE,
+                    _ => 0,
+                };
+                let size = ALONZO_ENTRY_SIZE_WITHOUT_VALUE + alonzo_value_size(&self.value) + datum;
+                Lovelace(size.saturating_mul(cost))
+            }
+        }
+    }
+
+    /// Babbage CBOR of the output, carrying `coin` lovelaces.
+    fn to_cbor(&self, coin: u64) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        let is_legacy = !matches!(self.datum, OutputDatum::Inline(_)) && self.script_ref.is_none();
+
+        if is_legacy {
+            let has_hash = matches!(self.datum, OutputDatum::Hash(_));
+            encoder.array(if has_hash { 3 } else { 2 }).bytes(&self.address);
+            encode_value(&mut encoder, coin, &self.value);
+            if let OutputDatum::Hash(hash) = &self.datum {
+                encoder.bytes(hash);
+            }
+        } else {
+            let length =
+                2 + (self.datum != OutputDatum::None) as usize + self.script_ref.is_some() as usize;
+            encoder.map(length).unsigned(0).bytes(&self.address).unsigned(1);
+            encode_value(&mut encoder, coin, &self.value);
+            match &self.datum {
+                OutputDatum::None => {}
+                OutputDatum::Hash(hash) => {
+                    encoder.unsigned(2).array(2).unsigned(0).bytes(hash);
+                }
+                OutputDatum::Inline(datum) => {
+                    encoder.unsigned(2).array(2).unsigned(1).tag(24).bytes(datum);
+                }
+            }
+            if let Some(script) = &self.script_ref {
+                encoder.unsigned(3).tag(24).bytes(script);
+            }
+        }
+        encoder.into_bytes()
+    }
+}
+
+impl EpochParameters {
+    /// Minimum lovelaces of an output with these parameters, see [`TxOutput::min_lovelace`].
+    pub fn min_utxo(&self, output: &TxOutput) -> Result<Lovelace, ParseError> {
+        UtxoCost::from_parameters(self).map(|cost| output.min_lovelace(cost))
+    }
+}
+
+// `coin` or `[coin, { policy_id => { asset_name => quantity } }]`
+fn encode_value(encoder: &mut Encoder, coin: u64, value: &Value) {
+    if value.is_ada_only() {
+        encoder.unsigned(coin);
+        return;
+    }
+
+    let mut policies: BTreeMap<&str, Vec<(&AssetId, u64)>> = BTreeMap::new();
+    for (asset, quantity) in value.assets() {
+        policies.entry(&asset.policy_id).or_default().push((asset, quantity));
+    }
+
+    encoder.array(2).unsigned(coin).map(policies.len());
+    for (policy_id, assets) in policies {
+        // Safety: validated hex
+        encoder.bytes(&decode_hex(policy_id).unwrap_or_default()).map(assets.len());
+        for (asset, quantity) in assets {
+            encoder.bytes(&asset.asset_name_bytes()).unsigned(quantity);
+        }
+    }
+}
+
+// Words of a value in the Alonzo formula
+fn alonzo_value_size(value: &Value) -> u64 {
+    if value.is_ada_only() {
+        return ALONZO_COIN_SIZE;
+    }
+
+    let assets = value.assets().count() as u64;
+    let policies = value.assets().map(|(asset, _)| &asset.policy_id).collect::<BTreeSet<_>>();
+    let names = value.assets().map(|(asset, _)| &asset.asset_name).collect::<BTreeSet<_>>();
+    let names_length = names.iter().map(|name| name.len() as u64 / 2).sum::<u64>();
+
+    let bytes = assets * 12 + names_length + policies.len() as u64 * ALONZO_POLICY_ID_SIZE;
+    6 + bytes.div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_ADDRESS: &str = "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x";
+    const ENTERPRISE_ADDRESS: &str = "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8";
+    const POLICY_ID: &str = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7";
+
+    fn value_with(asset_names: &[&str]) -> Value {
+        asset_names.iter().fold(Value::new(), |value, name| {
+            value.with_asset(AssetId::new(POLICY_ID, name).unwrap(), 1).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_babbage_min_lovelace() {
+        let cost = UtxoCost::PerByte(4310);
+
+        let base = TxOutput::new(BASE_ADDRESS, Value::new()).unwrap();
+        assert_eq!(base.min_lovelace(cost), Lovelace(969_750));
+        let enterprise = TxOutput::new(ENTERPRISE_ADDRESS, Value::new()).unwrap();
+        assert_eq!(enterprise.min_lovelace(cost), Lovelace(849_070));
+
+        // One asset named "nutcoin": 107 bytes
+        let tokens = TxOutput::new(BASE_ADDRESS, value_with(&["6e7574636f696e"])).unwrap();
+        assert_eq!(tokens.min_lovelace(cost), Lovelace((160 + 107) * 4310));
+
+        // Datum hash in the legacy format, 34 more bytes
+        let hash: DatumHash =
+            "923918e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec".parse().unwrap();
+        let with_hash = base.clone().with_datum_hash(&hash);
+        assert_eq!(with_hash.min_lovelace(cost), Lovelace(969_750 + 34 * 4310));
+
+        // Inline datum `42` in the map format: 67 bytes of address and amount, 8 bytes of datum
+        let inline = base.with_inline_datum(vec![0x18, 0x2a]);
+        assert_eq!(inline.min_lovelace(cost), Lovelace((160 + 67 + 8) * 4310));
+    }
+
+    #[test]
+    fn test_babbage_amount_encoding_size() {
+        // The amount goes from 5 to 9 bytes of CBOR at 2^32 lovelaces
+        let output = TxOutput::new(ENTERPRISE_ADDRESS, Value::new()).unwrap();
+        let cost = UtxoCost::PerByte(22_000_000);
+        assert_eq!(output.min_lovelace(cost), Lovelace((160 + 41) * 22_000_000));
+    }
+
+    #[test]
+    fn test_alonzo_min_lovelace() {
+        let cost = UtxoCost::PerWord(34482);
+
+        let ada_only = TxOutput::new(BASE_ADDRESS, Value::new()).unwrap();
+        assert_eq!(ada_only.min_lovelace(cost), Lovelace(999_978));
+
+        let hash: DatumHash =
+            "923918e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec".parse().unwrap();
+        assert_eq!(ada_only.with_datum_hash(&hash).min_lovelace(cost), Lovelace(1_344_798));
+
+        // One policy, one asset with a 32 bytes name
+        let tokens = TxOutput::new(BASE_ADDRESS, value_with(&[&"ab".repeat(32)])).unwrap();
+        assert_eq!(tokens.min_lovelace(cost), Lovelace(1_448_244));
+    }
+}
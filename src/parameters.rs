@@ -0,0 +1,242 @@
+//! Changes of protocol parameters between epochs, see [`diff`].
+
+use std::{fmt, ops::RangeInclusive, time::Duration};
+
+use futures::stream::{self, Stream, StreamExt};
+use futures_timer::Delay;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{BlockFrostApi, EpochParameters, Integer};
+
+// Parameters requested at the same time by `protocol_parameter_history`, as many as `Lister`
+const CONCURRENT_REQUESTS: usize = 10;
+
+// Fields that are not parameters, or that change every epoch
+const IGNORED_FIELDS: [&str; 2] = ["epoch", "nonce"];
+
+/// A protocol parameter that changed at the start of an epoch.
+///
+/// Nested values, e.g. cost models, are compared leaf by leaf, their `field` is the path joined
+/// by dots, e.g. `cost_models.PlutusV2.addInteger-cpu-arguments-intercept`, with array indices.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterChange {
+    /// First epoch with the new value.
+    pub epoch: i64,
+    /// Name of the parameter, as in [`EpochParameters`].
+    pub field: String,
+    /// Value in the previous epoch, `null` if absent.
+    pub old: JsonValue,
+    /// Value in `epoch`, `null` if absent.
+    pub new: JsonValue,
+}
+
+impl fmt::Display for ParameterChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "epoch {}: {} {} → {}", self.epoch, self.field, self.old, self.new)
+    }
+}
+
+/// Parameters that differ from `old` to `new`, sorted by field, in the epoch of `new`.
+///
+/// The epoch number and nonce are not compared.
+pub fn diff(old: &EpochParameters, new: &EpochParameters) -> Vec<ParameterChange> {
+    let mut old_fields = Map::new();
+    let mut new_fields = Map::new();
+    // Safety: serializing the derived types to JSON values does not fail
+    flatten(String::new(), serde_json::to_value(old).unwrap_or_default(), &mut old_fields);
+    flatten(String::new(), serde_json::to_value(new).unwrap_or_default(), &mut new_fields);
+
+    let mut fields: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|field| !IGNORED_FIELDS.contains(&field.as_str()))
+        .filter_map(|field| {
+            let old_value = old_fields.get(field).cloned().unwrap_or_default();
+            let new_value = new_fields.get(field).cloned().unwrap_or_default();
+            (old_value != new_value).then(|| ParameterChange {
+                epoch: new.epoch,
+                field: field.clone(),
+                old: old_value,
+                new: new_value,
+            })
+        })
+        .collect()
+}
+
+impl BlockFrostApi {
+    /// Changes of the protocol parameters in a range of epochs, from the parameters of the first
+    /// epoch to those of the last one, in order.
+    ///
+    /// Requests the [`epochs_parameters`](BlockFrostApi::epochs_parameters) of each epoch, up to
+    /// 10 at the same time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// for change in api.protocol_parameter_history(400..=450).await? {
+    ///     println!("{}", change);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn protocol_parameter_history(
+        &self,
+        epochs: RangeInclusive<Integer>,
+    ) -> crate::Result<Vec<ParameterChange>> {
+        let mut parameters = stream::iter(epochs)
+            .map(|epoch| self.epochs_parameters(epoch))
+            .buffered(CONCURRENT_REQUESTS);
+
+        let mut changes = Vec::new();
+        let mut previous = None;
+        while let Some(current) = parameters.next().await {
+            let current = current?;
+            if let Some(previous) = &previous {
+                changes.extend(diff(previous, &current));
+            }
+            previous = Some(current);
+        }
+        Ok(changes)
+    }
+
+    /// Watch the [`epochs_latest_parameters`](BlockFrostApi::epochs_latest_parameters), requested
+    /// every `interval`, and yield the changes when a new epoch changes them.
+    ///
+    /// The first request only sets the current parameters. Failed requests are yielded and the
+    /// stream goes on.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// use blockfrost::stream::StreamExt;
+    ///
+    /// let mut updates = Box::pin(api.watch_protocol_parameters(Duration::from_secs(600)));
+    /// while let Some(changes) = updates.next().await {
+    ///     for change in changes? {
+    ///         println!("{}", change);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_protocol_parameters(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = crate::Result<Vec<ParameterChange>>> + '_ {
+        stream::unfold(
+            (None::<EpochParameters>, true),
+            move |(mut previous, mut is_first)| async move {
+                loop {
+                    if !is_first {
+                        Delay::new(interval).await;
+                    }
+                    is_first = false;
+
+                    let current = match self.epochs_latest_parameters().await {
+                        Ok(current) => current,
+                        Err(error) => return Some((Err(error), (previous, is_first))),
+                    };
+                    let changes = match &previous {
+                        Some(previous) if previous.epoch != current.epoch => {
+                            diff(previous, &current)
+                        }
+                        _ => Vec::new(),
+                    };
+                    previous = Some(current);
+
+                    if !changes.is_empty() {
+                        return Some((Ok(changes), (previous, is_first)));
+                    }
+                }
+            },
+        )
+    }
+}
+
+// Insert the leaves of a JSON value, keyed by their path
+fn flatten(path: String, value: JsonValue, fields: &mut Map<String, JsonValue>) {
+    let join = |key: &dyn fmt::Display| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match value {
+        JsonValue::Object(object) if !object.is_empty() => {
+            for (key, value) in object {
+                flatten(join(&key), value, fields);
+            }
+        }
+        JsonValue::Array(array) if !array.is_empty() => {
+            for (index, value) in array.into_iter().enumerate() {
+                flatten(join(&index), value, fields);
+            }
+        }
+        value => {
+            fields.insert(path, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parameters(epoch: i64, min_fee_a: i64, cost_model: JsonValue) -> EpochParameters {
+        serde_json::from_value(json!({
+            "epoch": epoch,
+            "min_fee_a": min_fee_a,
+            "min_fee_b": 155381,
+            "max_block_size": 90112,
+            "max_tx_size": 16384,
+            "max_block_header_size": 1100,
+            "key_deposit": "2000000",
+            "pool_deposit": "500000000",
+            "e_max": 18,
+            "n_opt": 500,
+            "a0": 0.3,
+            "rho": 0.003,
+            "tau": 0.2,
+            "decentralisation_param": 0,
+            "extra_entropy": null,
+            "protocol_major_ver": 8,
+            "protocol_minor_ver": 0,
+            "min_utxo": "4310",
+            "min_pool_cost": "170000000",
+            "nonce": format!("{:064x}", epoch),
+            "cost_models_raw": { "PlutusV2": cost_model },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = parameters(400, 44, json!([1, 2]));
+        let new = parameters(401, 45, json!([1, 3, 4]));
+
+        let changes = diff(&old, &new);
+        let summary: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            summary,
+            [
+                "epoch 401: cost_models_raw.PlutusV2.1 2 → 3",
+                "epoch 401: cost_models_raw.PlutusV2.2 null → 4",
+                "epoch 401: min_fee_a 44 → 45",
+            ]
+        );
+
+        // Only the epoch and nonce differ
+        assert_eq!(diff(&old, &parameters(401, 44, json!([1, 2]))), []);
+    }
+}
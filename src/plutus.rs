@@ -0,0 +1,962 @@
+//! Plutus data, the structured values used in datums and redeemers.
+//!
+//! The API returns datums as hex-encoded CBOR, e.g.
+//! [`AddressUtxo::inline_datum`](crate::AddressUtxo::inline_datum), which
+//! [`PlutusData::from_cbor_hex`] decodes, or as hashes that
+//! [`resolve_datum`](crate::AddressUtxo::resolve_datum) looks up. Values map onto user types by
+//! implementing [`FromPlutusData`].
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    cbor::{self, to_usize, unexpected, Decoder, Encoder, Header},
+    utils::{blake2b_256, decode_hex, encode_hex},
+    AddressUtxo, BlockFrostApi, JsonMap, JsonValue, ParseError, ScriptDatum, ScriptDatumCbor,
+    TransactionUtxosOutput,
+};
+
+// Nesting limit, so malicious data can't overflow the stack
+const MAX_DEPTH: usize = 256;
+
+// Byte strings are split into chunks of this size when encoded
+const BYTES_CHUNK_SIZE: usize = 64;
+
+// Largest power of 10 that fits in a u64
+const DECIMAL_GROUP: u128 = 10_000_000_000_000_000_000;
+
+/// A Plutus data value.
+///
+/// Integers are arbitrary-precision, see [`BigInt`].
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::plutus::PlutusData;
+///
+/// // Constructor 0 with fields [42, h'cafe']
+/// let datum = PlutusData::from_cbor_hex("d8799f182a42cafeff").unwrap();
+///
+/// let (constructor, fields) = datum.as_constr().unwrap();
+/// assert_eq!(constructor, 0);
+/// assert_eq!(fields[0].as_integer(), Some(42));
+/// assert_eq!(fields[1].as_bytes(), Some(&[0xca, 0xfe][..]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PlutusData {
+    /// Constructor of a sum type, with its index and fields.
+    Constr {
+        constructor: u64,
+        fields: Vec<PlutusData>,
+    },
+    /// Key-value pairs, in their on-chain order.
+    Map(Vec<(PlutusData, PlutusData)>),
+    List(Vec<PlutusData>),
+    Integer(BigInt),
+    Bytes(Vec<u8>),
+}
+
+impl PlutusData {
+    /// Decode from CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ParseError> {
+        let mut decoder = Decoder::new(bytes);
+        let data = decode(&mut decoder, 0).map_err(|error| error_from_cbor(&error))?;
+
+        if !decoder.is_finished() {
+            return Err(error("trailing bytes after the data"));
+        }
+        Ok(data)
+    }
+
+    /// Decode from hex-encoded CBOR, as returned by the API.
+    pub fn from_cbor_hex(hex: &str) -> Result<Self, ParseError> {
+        let bytes = decode_hex(hex).ok_or_else(|| error(format!("'{}' is not hex", hex)))?;
+        Self::from_cbor(&bytes)
+    }
+
+    /// Constructor index and fields, if this is a constructor.
+    pub fn as_constr(&self) -> Option<(u64, &[PlutusData])> {
+        match self {
+            PlutusData::Constr { constructor, fields } => Some((*constructor, fields)),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&[(PlutusData, PlutusData)]> {
+        match self {
+            PlutusData::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[PlutusData]> {
+        match self {
+            PlutusData::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// The integer, if it fits in an `i128`, see [`as_big_integer`](PlutusData::as_big_integer).
+    pub fn as_integer(&self) -> Option<i128> {
+        self.as_big_integer()?.to_i128()
+    }
+
+    pub fn as_big_integer(&self) -> Option<&BigInt> {
+        match self {
+            PlutusData::Integer(integer) => Some(integer),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            PlutusData::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Bytes as UTF-8 text, metadata standards store strings this way.
+    pub fn as_utf8(&self) -> Option<&str> {
+        std::str::from_utf8(self.as_bytes()?).ok()
+    }
+
+    /// Encode to CBOR, the way Plutus does: non-empty lists and constructor fields with
+    /// indefinite length, integers beyond 64 bits as bignums, and byte strings longer than 64
+    /// bytes in chunks.
+    ///
+    /// Other encodings of the same value exist, so hashing a re-encoded datum may not give the
+    /// datum hash found on chain.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        encode(self, &mut encoder);
+        encoder.into_bytes()
+    }
+
+    /// Encode to hex-encoded CBOR, see [`to_cbor`](PlutusData::to_cbor).
+    pub fn to_cbor_hex(&self) -> String {
+        encode_hex(&self.to_cbor())
+    }
+
+    /// Convert from the "detailed schema" JSON of `cardano-cli`: `{ "constructor": 0, "fields":
+    /// [..] }`, `{ "map": [{ "k": .., "v": .. }] }`, `{ "list": [..] }`, `{ "int": 42 }` or
+    /// `{ "bytes": "cafe" }`.
+    ///
+    /// Integers can also be decimal strings, for those that don't fit in 64 bits.
+    pub fn from_json_detailed(json: &JsonValue) -> Result<Self, ParseError> {
+        let invalid = || error(format!("{} is not a detailed schema value", json));
+        let object = json.as_object().ok_or_else(invalid)?;
+
+        if object.len() == 2 {
+            let constructor = object.get("constructor").and_then(JsonValue::as_u64);
+            let fields = object.get("fields").and_then(JsonValue::as_array);
+            return match (constructor, fields) {
+                (Some(constructor), Some(fields)) => Ok(PlutusData::Constr {
+                    constructor,
+                    fields: fields
+                        .iter()
+                        .map(Self::from_json_detailed)
+                        .collect::<Result<_, _>>()?,
+                }),
+                _ => Err(invalid()),
+            };
+        }
+
+        let (kind, value) = match object.iter().next() {
+            Some(entry) if object.len() == 1 => entry,
+            _ => return Err(invalid()),
+        };
+        match (kind.as_str(), value) {
+            ("int", JsonValue::Number(number)) => match (number.as_i64(), number.as_u64()) {
+                (Some(integer), _) => Ok(PlutusData::Integer(integer.into())),
+                (_, Some(integer)) => Ok(PlutusData::Integer(integer.into())),
+                _ => Err(error(format!("{} is not an integer", number))),
+            },
+            ("int", JsonValue::String(text)) => text.parse().map(PlutusData::Integer),
+            ("bytes", JsonValue::String(hex)) => decode_hex(hex)
+                .map(PlutusData::Bytes)
+                .ok_or_else(|| error(format!("'{}' is not hex", hex))),
+            ("list", JsonValue::Array(items)) => {
+                items.iter().map(Self::from_json_detailed).collect::<Result<_, _>>().map(Self::List)
+            }
+            ("map", JsonValue::Array(entries)) => entries
+                .iter()
+                .map(|entry| match (entry.get("k"), entry.get("v")) {
+                    (Some(key), Some(value)) => {
+                        Ok((Self::from_json_detailed(key)?, Self::from_json_detailed(value)?))
+                    }
+                    _ => Err(error(format!("{} is not a map entry with 'k' and 'v'", entry))),
+                })
+                .collect::<Result<_, _>>()
+                .map(Self::Map),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Convert to the "detailed schema" JSON of `cardano-cli`, see
+    /// [`from_json_detailed`](PlutusData::from_json_detailed).
+    ///
+    /// Integers that don't fit in 64 bits become decimal strings.
+    pub fn to_json_detailed(&self) -> JsonValue {
+        let mut object = JsonMap::new();
+        match self {
+            PlutusData::Constr { constructor, fields } => {
+                object.insert("constructor".to_owned(), (*constructor).into());
+                let fields = fields.iter().map(Self::to_json_detailed).collect();
+                object.insert("fields".to_owned(), fields);
+            }
+            PlutusData::Map(entries) => {
+                let entries = entries.iter().map(|(key, value)| {
+                    let mut entry = JsonMap::new();
+                    entry.insert("k".to_owned(), key.to_json_detailed());
+                    entry.insert("v".to_owned(), value.to_json_detailed());
+                    JsonValue::Object(entry)
+                });
+                object.insert("map".to_owned(), entries.collect());
+            }
+            PlutusData::List(items) => {
+                object
+                    .insert("list".to_owned(), items.iter().map(Self::to_json_detailed).collect());
+            }
+            PlutusData::Integer(integer) => {
+                let small = integer.to_i128();
+                let integer = match (
+                    small.and_then(|small| i64::try_from(small).ok()),
+                    small.and_then(|small| u64::try_from(small).ok()),
+                ) {
+                    (Some(integer), _) => integer.into(),
+                    (_, Some(integer)) => integer.into(),
+                    _ => integer.to_string().into(),
+                };
+                object.insert("int".to_owned(), integer);
+            }
+            PlutusData::Bytes(bytes) => {
+                object.insert("bytes".to_owned(), encode_hex(bytes).into());
+            }
+        }
+        JsonValue::Object(object)
+    }
+
+    /// Convert into a type implementing [`FromPlutusData`].
+    pub fn parse<T: FromPlutusData>(&self) -> Result<T, ParseError> {
+        T::from_plutus_data(self)
+    }
+
+    /// Convert a list into a `Vec` of a type implementing [`FromPlutusData`].
+    pub fn parse_list<T: FromPlutusData>(&self) -> Result<Vec<T>, ParseError> {
+        let items = self.as_list().ok_or_else(|| error("expected a list"))?;
+        items.iter().map(T::from_plutus_data).collect()
+    }
+
+    /// Fields of a constructor, checking its index and number of fields, to convert them one by
+    /// one with [`ConstrFields::field`].
+    pub fn constr_fields(
+        &self,
+        constructor: u64,
+        length: usize,
+    ) -> Result<ConstrFields<'_>, ParseError> {
+        match self.as_constr() {
+            Some((index, fields)) if index == constructor && fields.len() == length => {
+                Ok(ConstrFields { fields: fields.iter(), index: 0 })
+            }
+            _ => Err(error(format!(
+                "expected constructor {} with {} fields, found {}",
+                constructor,
+                length,
+                self.to_json_detailed()
+            ))),
+        }
+    }
+}
+
+/// An integer of any size, as Plutus integers are, stored as a sign and magnitude.
+///
+/// Converts from the primitive integers, and from and to decimal strings.
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::plutus::BigInt;
+///
+/// let big: BigInt = "-340282366920938463463374607431768211456".parse().unwrap();
+/// assert!(big.is_negative());
+/// assert_eq!(big.to_i128(), None);
+/// assert_eq!(big.to_string(), "-340282366920938463463374607431768211456");
+///
+/// assert_eq!(BigInt::from(42).to_i128(), Some(42));
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    // Big-endian, without leading zeros, so zero is empty
+    magnitude: Vec<u8>,
+}
+
+impl BigInt {
+    /// Build from a sign and the big-endian bytes of the absolute value.
+    pub fn from_sign_magnitude(negative: bool, magnitude: &[u8]) -> Self {
+        let magnitude = trim_leading_zeros(magnitude).to_vec();
+        Self { negative: negative && !magnitude.is_empty(), magnitude }
+    }
+
+    /// Whether the integer is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Big-endian bytes of the absolute value, without leading zeros.
+    pub fn magnitude(&self) -> &[u8] {
+        &self.magnitude
+    }
+
+    /// The integer as an `i128`, `None` if it doesn't fit.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 16 {
+            return None;
+        }
+        let magnitude = self.magnitude.iter().fold(0, |value, byte| (value << 8) | *byte as u128);
+        match i128::try_from(magnitude) {
+            Ok(magnitude) if self.negative => Some(-magnitude),
+            Ok(magnitude) => Some(magnitude),
+            Err(_) if self.negative && magnitude == i128::MIN.unsigned_abs() => Some(i128::MIN),
+            Err(_) => None,
+        }
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(integer: i128) -> Self {
+        Self::from_sign_magnitude(integer < 0, &integer.unsigned_abs().to_be_bytes())
+    }
+}
+
+macro_rules! big_int_from {
+    ($($type:ty)*) => {
+        $(
+            impl From<$type> for BigInt {
+                fn from(integer: $type) -> Self {
+                    i128::from(integer).into()
+                }
+            }
+        )*
+    };
+}
+
+big_int_from! { i64 u64 i32 u32 }
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Digits in groups of 19, the most that fit in a u64, least significant first
+        let mut groups = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let mut remainder = 0;
+            for byte in &mut magnitude {
+                let value = (remainder << 8) | *byte as u128;
+                *byte = (value / DECIMAL_GROUP) as u8;
+                remainder = value % DECIMAL_GROUP;
+            }
+            groups.push(remainder);
+            magnitude = trim_leading_zeros(&magnitude).to_vec();
+        }
+
+        let mut text = String::from(if self.negative { "-" } else { "" });
+        match groups.split_last() {
+            Some((first, rest)) => {
+                text.push_str(&first.to_string());
+                rest.iter().rev().for_each(|group| text.push_str(&format!("{:019}", group)));
+            }
+            None => text.push('0'),
+        }
+        formatter.pad(&text)
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, formatter)
+    }
+}
+
+/// Parse a decimal integer, optionally starting with `-`.
+impl FromStr for BigInt {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return Err(error(format!("'{}' is not an integer", text)));
+        }
+
+        let mut magnitude = Vec::new();
+        for digit in digits.bytes() {
+            let mut carry = (digit - b'0') as u16;
+            for byte in magnitude.iter_mut().rev() {
+                let value = *byte as u16 * 10 + carry;
+                *byte = value as u8;
+                carry = value >> 8;
+            }
+            if carry > 0 {
+                magnitude.insert(0, carry as u8);
+            }
+        }
+        Ok(Self::from_sign_magnitude(negative, &magnitude))
+    }
+}
+
+/// Conversion from [`PlutusData`], e.g. for the datums of a script.
+///
+/// Implemented for [`PlutusData`] itself, integers ([`BigInt`] and primitive ones), bytes (`Vec<u8>` and arrays of an exact
+/// length, e.g. hashes), UTF-8 [`String`]s stored as bytes, and the Plutus encodings of [`bool`]
+/// and [`Option`]. Lists convert with [`PlutusData::parse_list`].
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::{
+///     plutus::{FromPlutusData, PlutusData},
+///     ParseError,
+/// };
+///
+/// // data Order = Order { owner :: PubKeyHash, price :: Integer, partial :: Bool }
+/// struct Order {
+///     owner: [u8; 28],
+///     price: u64,
+///     partial: bool,
+/// }
+///
+/// impl FromPlutusData for Order {
+///     fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+///         let mut fields = data.constr_fields(0, 3)?;
+///         Ok(Order { owner: fields.field()?, price: fields.field()?, partial: fields.field()? })
+///     }
+/// }
+///
+/// let datum = PlutusData::from_cbor_hex(&format!("d8799f581c{}1a000f4240d87a80ff", "ab".repeat(28)))
+///     .unwrap();
+/// let order: Order = datum.parse().unwrap();
+///
+/// assert_eq!(order.owner, [0xab; 28]);
+/// assert_eq!(order.price, 1_000_000);
+/// assert!(order.partial);
+/// ```
+pub trait FromPlutusData: Sized {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError>;
+}
+
+/// The fields of a constructor, converted in order, created by [`PlutusData::constr_fields`].
+#[derive(Clone, Debug)]
+pub struct ConstrFields<'a> {
+    fields: std::slice::Iter<'a, PlutusData>,
+    index: usize,
+}
+
+impl ConstrFields<'_> {
+    /// Convert the next field.
+    pub fn field<T: FromPlutusData>(&mut self) -> Result<T, ParseError> {
+        self.next_field(T::from_plutus_data)
+    }
+
+    /// Convert the next field, a list.
+    pub fn field_list<T: FromPlutusData>(&mut self) -> Result<Vec<T>, ParseError> {
+        self.next_field(PlutusData::parse_list)
+    }
+
+    fn next_field<T>(
+        &mut self,
+        convert: impl FnOnce(&PlutusData) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let index = self.index;
+        self.index += 1;
+
+        let field = self.fields.next().ok_or_else(|| error(format!("no field {}", index)))?;
+        convert(field).map_err(|cause| error(format!("field {}: {}", index, cause.reason)))
+    }
+}
+
+impl FromPlutusData for PlutusData {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        Ok(data.clone())
+    }
+}
+
+impl FromPlutusData for BigInt {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        data.as_big_integer().cloned().ok_or_else(|| error("expected an integer"))
+    }
+}
+
+impl FromPlutusData for i128 {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        let integer = data.as_big_integer().ok_or_else(|| error("expected an integer"))?;
+        integer.to_i128().ok_or_else(|| error(format!("{} does not fit in i128", integer)))
+    }
+}
+
+// Integers that must fit in a smaller type
+macro_rules! integer_from_plutus_data {
+    ($($type:ty)*) => {
+        $(
+            impl FromPlutusData for $type {
+                fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+                    let integer = i128::from_plutus_data(data)?;
+                    integer.try_into().map_err(|_| {
+                        error(format!("{} does not fit in {}", integer, stringify!($type)))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+integer_from_plutus_data! { i64 u64 u32 }
+
+impl FromPlutusData for Vec<u8> {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        data.as_bytes().map(<[u8]>::to_vec).ok_or_else(|| error("expected bytes"))
+    }
+}
+
+impl<const N: usize> FromPlutusData for [u8; N] {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        let bytes = data.as_bytes().ok_or_else(|| error("expected bytes"))?;
+        bytes.try_into().map_err(|_| error(format!("expected {} bytes, found {}", N, bytes.len())))
+    }
+}
+
+impl FromPlutusData for String {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        data.as_utf8().map(str::to_owned).ok_or_else(|| error("expected UTF-8 bytes"))
+    }
+}
+
+/// `False` is constructor 0 and `True` is constructor 1, without fields.
+impl FromPlutusData for bool {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        match data.as_constr() {
+            Some((0, [])) => Ok(false),
+            Some((1, [])) => Ok(true),
+            _ => Err(error("expected a boolean constructor")),
+        }
+    }
+}
+
+/// `Just x` is constructor 0 with the field `x`, `Nothing` is constructor 1 without fields.
+impl<T: FromPlutusData> FromPlutusData for Option<T> {
+    fn from_plutus_data(data: &PlutusData) -> Result<Self, ParseError> {
+        match data.as_constr() {
+            Some((0, [value])) => T::from_plutus_data(value).map(Some),
+            Some((1, [])) => Ok(None),
+            _ => Err(error("expected a Just or Nothing constructor")),
+        }
+    }
+}
+
+impl ScriptDatum {
+    /// Parse [`json_value`](ScriptDatum::json_value).
+    pub fn datum(&self) -> Result<PlutusData, ParseError> {
+        PlutusData::from_json_detailed(&self.json_value)
+    }
+}
+
+impl ScriptDatumCbor {
+    /// Decode [`cbor`](ScriptDatumCbor::cbor).
+    pub fn datum(&self) -> Result<PlutusData, ParseError> {
+        PlutusData::from_cbor_hex(&self.cbor)
+    }
+}
+
+impl BlockFrostApi {
+    /// Fetch the datum with this hash and check that its CBOR hashes to it.
+    ///
+    /// Fails with [`Error::Parse`](crate::Error::Parse) when the returned datum doesn't match.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// let hash = "db583ad85881a96c73fbb26ab9e24d1120bb38f45385664bb9c797a2ea8d9a2d";
+    /// println!("{:?}", api.datum(hash).await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn datum(&self, datum_hash: &str) -> crate::Result<PlutusData> {
+        let cbor = self.scripts_datum_cbor(datum_hash).await?.cbor;
+        Ok(verified_datum(datum_hash, &cbor)?)
+    }
+}
+
+macro_rules! resolve_datum_methods {
+    ($($type:ident)*) => {
+        $(
+            impl $type {
+                #[doc = concat!(
+                    "The datum of the output, `None` if it has none.\n\nAn inline datum is",
+                    " decoded, a datum hash is fetched with [`datum`](BlockFrostApi::datum)."
+                )]
+                pub async fn resolve_datum(
+                    &self,
+                    api: &BlockFrostApi,
+                ) -> crate::Result<Option<PlutusData>> {
+                    match (&self.inline_datum, &self.data_hash) {
+                        (Some(inline_datum), _) => {
+                            Ok(Some(PlutusData::from_cbor_hex(inline_datum)?))
+                        }
+                        (None, Some(data_hash)) => api.datum(data_hash).await.map(Some),
+                        (None, None) => Ok(None),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+resolve_datum_methods! {
+    AddressUtxo
+    TransactionUtxosOutput
+}
+
+// Decode a datum, if its CBOR hashes to `datum_hash`
+fn verified_datum(datum_hash: &str, cbor_hex: &str) -> Result<PlutusData, ParseError> {
+    let bytes = decode_hex(cbor_hex).ok_or_else(|| error(format!("'{}' is not hex", cbor_hex)))?;
+    let hash = encode_hex(&blake2b_256(&bytes));
+    if !hash.eq_ignore_ascii_case(datum_hash) {
+        return Err(error(format!("hash {} does not match {}", hash, datum_hash)));
+    }
+    PlutusData::from_cbor(&bytes)
+}
+
+fn decode(decoder: &mut Decoder, depth: usize) -> cbor::Result<PlutusData> {
+    if depth > MAX_DEPTH {
+        return Err(cbor::error("data is nested too deeply"));
+    }
+
+    match decoder.peek_header()? {
+        Header::Unsigned(_) | Header::Negative(_) => match decoder.header()? {
+            Header::Unsigned(value) => Ok(PlutusData::Integer(value.into())),
+            Header::Negative(value) => Ok(PlutusData::Integer((-1 - i128::from(value)).into())),
+            _ => unreachable!("header was peeked"),
+        },
+        Header::Bytes(_) => decoder.bytes().map(PlutusData::Bytes),
+        Header::Array(_) => decode_list(decoder, depth).map(PlutusData::List),
+        Header::Map(_) => {
+            let length = decoder.map()?;
+            let mut entries = Vec::new();
+            while decoder.has_next(length, entries.len() as u64)? {
+                let key = decode(decoder, depth + 1)?;
+                let value = decode(decoder, depth + 1)?;
+                entries.push((key, value));
+            }
+            Ok(PlutusData::Map(entries))
+        }
+        Header::Tag(_) => {
+            let tag = decoder.tag()?;
+            let constructor = match tag {
+                // Big integers, in big-endian bytes, tag 3 holding -1 - n
+                2 => {
+                    let integer = BigInt::from_sign_magnitude(false, &decoder.bytes()?);
+                    return Ok(PlutusData::Integer(integer));
+                }
+                3 => {
+                    let magnitude = increment(trim_leading_zeros(&decoder.bytes()?));
+                    return Ok(PlutusData::Integer(BigInt::from_sign_magnitude(true, &magnitude)));
+                }
+                121..=127 => tag - 121,
+                1280..=1400 => tag - 1280 + 7,
+                // General form: [constructor, fields]
+                102 => {
+                    if decoder.array()? != Some(2) {
+                        return Err(cbor::error("expected a constructor with 2 elements"));
+                    }
+                    decoder.unsigned()?
+                }
+                _ => return Err(cbor::error(format!("unexpected tag {}", tag))),
+            };
+            let fields = decode_list(decoder, depth)?;
+            Ok(PlutusData::Constr { constructor, fields })
+        }
+        other => Err(unexpected("plutus data", other)),
+    }
+}
+
+fn encode(data: &PlutusData, encoder: &mut Encoder) {
+    match data {
+        PlutusData::Constr { constructor, fields } => {
+            match *constructor {
+                0..=6 => encoder.tag(121 + constructor),
+                7..=127 => encoder.tag(1280 + constructor - 7),
+                _ => encoder.tag(102).array(2).unsigned(*constructor),
+            };
+            encode_list(fields, encoder);
+        }
+        PlutusData::Map(entries) => {
+            encoder.map(entries.len());
+            for (key, value) in entries {
+                encode(key, encoder);
+                encode(value, encoder);
+            }
+        }
+        PlutusData::List(items) => encode_list(items, encoder),
+        PlutusData::Integer(integer) => {
+            // Negative integers are stored as -1 - n
+            let stored = if integer.negative {
+                decrement(&integer.magnitude)
+            } else {
+                integer.magnitude.clone()
+            };
+            let small = match stored.len() {
+                0..=8 => Some(stored.iter().fold(0, |value, byte| (value << 8) | *byte as u64)),
+                _ => None,
+            };
+            match (integer.negative, small) {
+                (false, Some(unsigned)) => encoder.unsigned(unsigned),
+                (true, Some(negative)) => encoder.negative(negative),
+                // Big integers, in big-endian bytes
+                (false, None) => encoder.tag(2).bytes(&stored),
+                (true, None) => encoder.tag(3).bytes(&stored),
+            };
+        }
+        PlutusData::Bytes(bytes) if bytes.len() <= BYTES_CHUNK_SIZE => {
+            encoder.bytes(bytes);
+        }
+        PlutusData::Bytes(bytes) => {
+            encoder.begin_bytes();
+            for chunk in bytes.chunks(BYTES_CHUNK_SIZE) {
+                encoder.bytes(chunk);
+            }
+            encoder.end();
+        }
+    }
+}
+
+fn encode_list(items: &[PlutusData], encoder: &mut Encoder) {
+    if items.is_empty() {
+        encoder.array(0);
+        return;
+    }
+    encoder.begin_array();
+    for item in items {
+        encode(item, encoder);
+    }
+    encoder.end();
+}
+
+fn decode_list(decoder: &mut Decoder, depth: usize) -> cbor::Result<Vec<PlutusData>> {
+    let length = decoder.array()?;
+    let mut items = Vec::with_capacity(to_usize(length.unwrap_or(0))?.min(1024));
+    while decoder.has_next(length, items.len() as u64)? {
+        items.push(decode(decoder, depth + 1)?);
+    }
+    Ok(items)
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+// Add one to big-endian bytes
+fn increment(bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    for byte in bytes.iter_mut().rev() {
+        let (value, overflow) = byte.overflowing_add(1);
+        *byte = value;
+        if !overflow {
+            return bytes;
+        }
+    }
+    bytes.insert(0, 1);
+    bytes
+}
+
+// Subtract one from big-endian bytes without leading zeros, which are not zero
+fn decrement(bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    for byte in bytes.iter_mut().rev() {
+        let (value, underflow) = byte.overflowing_sub(1);
+        *byte = value;
+        if !underflow {
+            break;
+        }
+    }
+    trim_leading_zeros(&bytes).to_vec()
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("plutus data", reason)
+}
+
+fn error_from_cbor(cbor_error: &ParseError) -> ParseError {
+    error(&cbor_error.reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(bytes: &[u8]) -> PlutusData {
+        PlutusData::Bytes(bytes.to_vec())
+    }
+
+    #[test]
+    fn test_decode_constructors() {
+        // Tags 121, 1280, 102 with constructor 200, and 126
+        let cases = [("d87980", 0), ("d9050080", 7), ("d8668218c880", 200), ("d87e80", 5)];
+        for (hex, expected) in cases {
+            let data = PlutusData::from_cbor_hex(hex).unwrap();
+            assert_eq!(data.as_constr().unwrap(), (expected, &[][..]), "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_decode_integers() {
+        let cases = [
+            ("00", 0),
+            ("3863", -100),
+            ("1bffffffffffffffff", u64::MAX as i128),
+            ("c249010000000000000000", 1 << 64),
+            ("c349010000000000000000", -1 - (1 << 64)),
+        ];
+        for (hex, expected) in cases {
+            let expected = PlutusData::Integer(expected.into());
+            assert_eq!(PlutusData::from_cbor_hex(hex).unwrap(), expected);
+        }
+
+        // Leading zeros
+        let data = PlutusData::from_cbor_hex("c343000001").unwrap();
+        assert_eq!(data.as_integer(), Some(-2));
+
+        // 17 significant bytes
+        let data = PlutusData::from_cbor_hex(&format!("c351{}", "ff".repeat(17))).unwrap();
+        assert_eq!(data.as_integer(), None);
+        let integer = data.as_big_integer().unwrap();
+        assert!(integer.is_negative());
+        assert_eq!(integer.magnitude(), [&[1][..], &[0; 17]].concat());
+        assert!(data.parse::<i128>().is_err());
+    }
+
+    #[test]
+    fn test_big_int() {
+        let cases = [
+            "0",
+            "-1",
+            "18446744073709551616",
+            "-170141183460469231731687303715884105728",
+            "-1000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ];
+        for text in cases {
+            assert_eq!(text.parse::<BigInt>().unwrap().to_string(), text);
+        }
+        assert_eq!("-0".parse::<BigInt>().unwrap(), BigInt::default());
+        assert_eq!("007".parse::<BigInt>().unwrap(), BigInt::from(7));
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(BigInt::from(i128::MAX).to_i128(), Some(i128::MAX));
+        assert_eq!(
+            "170141183460469231731687303715884105728".parse::<BigInt>().unwrap().to_i128(),
+            None
+        );
+
+        for text in ["", "-", "+1", "1.5", "1a"] {
+            assert!(text.parse::<BigInt>().is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let cases = [
+            "d8799f182a42cafeff",
+            "d9050080",
+            "d8668218c880",
+            "a2014002d87b9f01ff",
+            "9f3863c249010000000000000000c349010000000000000000ff",
+            "3bffffffffffffffff",
+            "c3490100000000000000ff",
+            "80",
+        ];
+        for hex in cases {
+            assert_eq!(PlutusData::from_cbor_hex(hex).unwrap().to_cbor_hex(), hex);
+        }
+
+        // Bignums of any size
+        let big = format!("c258{:x}{}", 100, "ab".repeat(100));
+        assert_eq!(PlutusData::from_cbor_hex(&big).unwrap().to_cbor_hex(), big);
+
+        // 65 bytes, in chunks of 64 and 1
+        let long = PlutusData::Bytes(vec![7; 65]);
+        let expected = format!("5f5840{}4107ff", "07".repeat(64));
+        assert_eq!(long.to_cbor_hex(), expected);
+        assert_eq!(PlutusData::from_cbor_hex(&expected).unwrap(), long);
+    }
+
+    #[test]
+    fn test_json_detailed() {
+        let json = serde_json::json!({ "constructor": 1, "fields": [
+            { "map": [{ "k": { "bytes": "cafe" }, "v": { "int": -1 } }] },
+            { "list": [
+                { "int": "-18446744073709551617" },
+                { "int": 18446744073709551615u64 },
+                { "int": "340282366920938463463374607431768211456" },
+            ] },
+        ]});
+        let data = PlutusData::from_json_detailed(&json).unwrap();
+
+        let (constructor, fields) = data.as_constr().unwrap();
+        assert_eq!(constructor, 1);
+        let integer = |integer: i128| PlutusData::Integer(integer.into());
+        assert_eq!(fields[0].as_map().unwrap()[0], (bytes(&[0xca, 0xfe]), integer(-1)));
+        assert_eq!(fields[1].as_list().unwrap()[0], integer(-1 - (1 << 64)));
+        let big = fields[1].as_list().unwrap()[2].as_big_integer().unwrap();
+        assert_eq!(big.magnitude(), [&[1][..], &[0; 16]].concat());
+        assert_eq!(data.to_json_detailed(), json);
+
+        assert!(PlutusData::from_json_detailed(&serde_json::json!({ "int": 1.5 })).is_err());
+        assert!(PlutusData::from_json_detailed(&serde_json::json!({ "fields": [] })).is_err());
+    }
+
+    #[test]
+    fn test_from_plutus_data() {
+        // Constr 0 [Just "nut", Nothing, [1, 2], h'cafe']
+        let data =
+            PlutusData::from_cbor_hex("d8799fd8799f436e7574ffd87a809f0102ff42cafeff").unwrap();
+        let mut fields = data.constr_fields(0, 4).unwrap();
+
+        assert_eq!(fields.field::<Option<String>>().unwrap(), Some("nut".to_owned()));
+        assert_eq!(fields.field::<Option<u64>>().unwrap(), None);
+        assert_eq!(fields.field_list::<u32>().unwrap(), [1, 2]);
+        let error = fields.field::<[u8; 28]>().unwrap_err();
+        assert_eq!(error.reason, "field 3: expected 28 bytes, found 2");
+        assert!(fields.field::<PlutusData>().is_err());
+
+        assert!(data.constr_fields(1, 4).is_err());
+        assert!(PlutusData::Integer((-1).into()).parse::<u64>().is_err());
+    }
+
+    #[test]
+    fn test_decode_nested() {
+        // {_ (_ h'6e61', h'6d'): [1, h'']}
+        let data = PlutusData::from_cbor_hex("bf5f426e61416dff820140ff").unwrap();
+        let expected = PlutusData::Map(vec![(
+            bytes(b"nam"),
+            PlutusData::List(vec![PlutusData::Integer(1.into()), bytes(b"")]),
+        )]);
+        assert_eq!(data, expected);
+        assert_eq!(data.as_map().unwrap()[0].0.as_utf8(), Some("nam"));
+
+        assert!(PlutusData::from_cbor_hex("6161").is_err());
+        assert!(PlutusData::from_cbor_hex("0000").is_err());
+        assert!(PlutusData::from_cbor_hex(&"81".repeat(1000)).is_err());
+    }
+
+    #[test]
+    fn test_verified_datum() {
+        let hash = "9e1199a988ba72ffd6e9c269cadb3b53b5f360ff99f112d9b2ee30c4d74ad88b";
+        let expected = PlutusData::Integer(42.into());
+        assert_eq!(verified_datum(hash, "182a").unwrap(), expected);
+        assert_eq!(verified_datum(&hash.to_uppercase(), "182a").unwrap(), expected);
+        assert!(verified_datum(hash, "182b").is_err());
+
+        let datum = ScriptDatum { json_value: serde_json::json!({ "int": 42 }) };
+        assert_eq!(datum.datum().unwrap(), expected);
+    }
+}
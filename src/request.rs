@@ -1,11 +1,11 @@
 //! Module for common requests logic.
 
-use std::{future::Future, thread};
+use std::{future::Future, thread, time::Instant};
 
 use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde_json::from_str as json_from;
 
-use crate::{json_error, process_error_response, reqwest_error, RetrySettings};
+use crate::{json_error, process_error_response, reqwest_error, RetrySettings, WithMeta};
 
 // Used only for simple and common GET requests.
 // Functions that require extra logic may not call this.
@@ -31,25 +31,68 @@ where
     }
 }
 
+// Same as send_get_request, but keeps the response metadata.
+pub(crate) fn send_get_request_with_meta<T>(
+    client: &Client,
+    url: String,
+    retry_settings: RetrySettings,
+) -> impl Future<Output = crate::Result<WithMeta<T>>> + Send
+where
+    T: serde::de::DeserializeOwned,
+{
+    let request = client.get(&url);
+
+    async move {
+        let start = Instant::now();
+        let (response, retries) = send_request_counting_retries(request, retry_settings)
+            .await
+            .map_err(|reason| reqwest_error(&url, reason))?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let final_url = response.url().to_string();
+        let text = response.text().await.map_err(|reason| reqwest_error(&url, reason))?;
+        let elapsed = start.elapsed();
+
+        if !status.is_success() {
+            return Err(process_error_response(&text, status, &url));
+        }
+        let data = json_from::<T>(&text).map_err(|reason| json_error(url, text, reason))?;
+
+        Ok(WithMeta { data, status, headers, url: final_url, retries, elapsed })
+    }
+}
+
 // Send requests with delayed retries, cloning the request builder only when necessary.
 pub(crate) async fn send_request_unprocessed(
     request: RequestBuilder,
     retry_settings: RetrySettings,
 ) -> reqwest::Result<Response> {
+    let (response, _retries) = send_request_counting_retries(request, retry_settings).await?;
+    Ok(response)
+}
+
+// Calls send_request_unprocessed but also returns how many retries were made
+async fn send_request_counting_retries(
+    request: RequestBuilder,
+    retry_settings: RetrySettings,
+) -> reqwest::Result<(Response, u64)> {
+    let mut retries = 0;
+
     for _ in 1..retry_settings.amount {
         let request = clone_request(&request);
-        let response = request.send().await;
+        let response = request.send().await?;
 
-        if let Err(err) = &response {
-            if let Some(StatusCode::TOO_MANY_REQUESTS) = err.status() {
-                thread::sleep(retry_settings.delay);
-                continue;
-            }
+        // Rate limited, the status is in the response, send() only fails on connection errors
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            thread::sleep(retry_settings.delay);
+            retries += 1;
+            continue;
         }
 
-        return response;
+        return Ok((response, retries));
     }
-    request.send().await
+    request.send().await.map(|response| (response, retries))
 }
 
 // Calls send_request_unprocessed but break is down
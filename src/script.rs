@@ -0,0 +1,551 @@
+//! Script hashes, computed locally to verify the scripts returned by the API.
+//!
+//! A script hash is the Blake2b-224 digest of the script bytes prefixed by the
+//! [`ScriptLanguage::tag`]. [`BlockFrostApi::verified_script`] fetches a script and checks that
+//! it hashes to the requested hash. Native scripts are parsed into a [`NativeScript`], which
+//! tells which signatures a transaction still needs.
+
+use std::collections::BTreeSet;
+
+use serde_json::json;
+
+use crate::{
+    cbor::{Decoder, Encoder},
+    utils::{blake2b_224, decode_hex, encode_hex},
+    BlockFrostApi, JsonValue, ParseError, ScriptType, Transaction,
+};
+
+// Nesting limit of native scripts, so malicious JSON can't overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// Language of a script, which prefixes its bytes when hashed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScriptLanguage {
+    /// Native (timelock) script, a tree of signatures and validity intervals.
+    Native,
+    PlutusV1,
+    PlutusV2,
+    PlutusV3,
+}
+
+impl ScriptLanguage {
+    /// The byte prefixed to the script bytes when hashed.
+    pub fn tag(self) -> u8 {
+        match self {
+            ScriptLanguage::Native => 0,
+            ScriptLanguage::PlutusV1 => 1,
+            ScriptLanguage::PlutusV2 => 2,
+            ScriptLanguage::PlutusV3 => 3,
+        }
+    }
+
+    // Candidate languages of a script type, all the Plutus versions when it's unversioned
+    fn candidates(type_: &ScriptType) -> &'static [ScriptLanguage] {
+        match type_ {
+            ScriptType::Timelock => &[ScriptLanguage::Native],
+            ScriptType::PlutusV1 => &[ScriptLanguage::PlutusV1],
+            ScriptType::PlutusV2 => &[ScriptLanguage::PlutusV2],
+            ScriptType::PlutusV3 => &[ScriptLanguage::PlutusV3],
+            ScriptType::Plutus => {
+                &[ScriptLanguage::PlutusV1, ScriptLanguage::PlutusV2, ScriptLanguage::PlutusV3]
+            }
+        }
+    }
+}
+
+/// Hex-encoded hash of a script, given its serialized bytes.
+///
+/// For Plutus scripts, the bytes are the CBOR byte string wrapping the flat-encoded program, as
+/// returned by [`plutus_script_bytes`].
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::script::{plutus_script_bytes, script_hash, ScriptLanguage};
+///
+/// let bytes = plutus_script_bytes("4e4d01000033222220051200120011").unwrap();
+/// assert_eq!(
+///     script_hash(ScriptLanguage::PlutusV1, &bytes),
+///     "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656"
+/// );
+/// ```
+pub fn script_hash(language: ScriptLanguage, bytes: &[u8]) -> String {
+    let mut tagged = Vec::with_capacity(bytes.len() + 1);
+    tagged.push(language.tag());
+    tagged.extend_from_slice(bytes);
+    encode_hex(&blake2b_224(&tagged))
+}
+
+/// Bytes of a Plutus script to hash, from its hex-encoded CBOR, e.g.
+/// [`ScriptCbor::cbor`](crate::ScriptCbor::cbor).
+///
+/// Scripts are often wrapped twice in a CBOR byte string, as the API returns them, only the inner
+/// byte string is hashed.
+pub fn plutus_script_bytes(cbor_hex: &str) -> Result<Vec<u8>, ParseError> {
+    let bytes = decode_hex(cbor_hex).ok_or_else(|| error("CBOR is not hex-encoded"))?;
+
+    let mut decoder = Decoder::new(&bytes);
+    let content = decoder.bytes().map_err(|_| error("CBOR is not a byte string"))?;
+    if !decoder.is_finished() {
+        return Err(error("CBOR has trailing bytes"));
+    }
+
+    let mut inner = Decoder::new(&content);
+    match inner.bytes() {
+        Ok(_) if inner.is_finished() => Ok(content),
+        _ => Ok(bytes),
+    }
+}
+
+/// A native (timelock) script, parsed from its JSON in the `cardano-cli` format, e.g.
+/// [`ScriptJson::json`](crate::ScriptJson::json).
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::script::{Evaluation, NativeScript, ValidityInterval};
+/// use serde_json::json;
+///
+/// let alice = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+/// let bob = "654891a4db2ea44b5263f4079a33efa0358ba90769e3d8f86a4a0f81";
+/// let script = NativeScript::from_json(&json!({
+///     "type": "all",
+///     "scripts": [
+///         { "type": "sig", "keyHash": alice },
+///         { "type": "sig", "keyHash": bob },
+///     ],
+/// }))
+/// .unwrap();
+///
+/// let evaluation = script.evaluate(&[alice], &ValidityInterval::default());
+/// assert_eq!(evaluation, Evaluation::MissingSignatures(vec![bob.to_owned()]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NativeScript {
+    /// Signed by the key with this hex-encoded hash, `sig`.
+    Signature(String),
+    /// Every script is satisfied, `all`.
+    All(Vec<NativeScript>),
+    /// At least one script is satisfied, `any`.
+    Any(Vec<NativeScript>),
+    /// At least `required` scripts are satisfied, `atLeast`.
+    AtLeast { required: u64, scripts: Vec<NativeScript> },
+    /// The transaction is only valid from this slot on, `after`.
+    After(u64),
+    /// The transaction is only valid before this slot, `before`.
+    Before(u64),
+}
+
+/// Validity interval of a transaction, in slots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidityInterval {
+    /// First slot the transaction is valid in.
+    pub invalid_before: Option<u64>,
+    /// First slot the transaction is no longer valid in.
+    pub invalid_hereafter: Option<u64>,
+}
+
+impl From<&Transaction> for ValidityInterval {
+    fn from(transaction: &Transaction) -> Self {
+        let slot = |slot: &Option<String>| slot.as_deref().and_then(|slot| slot.parse().ok());
+        ValidityInterval {
+            invalid_before: slot(&transaction.invalid_before),
+            invalid_hereafter: slot(&transaction.invalid_hereafter),
+        }
+    }
+}
+
+/// Result of [`NativeScript::evaluate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Evaluation {
+    Satisfied,
+    /// Satisfied once these hex-encoded key hashes sign too, sorted.
+    ///
+    /// No smaller set of signatures satisfies the script, signatures shared by several branches
+    /// of `any` and `atLeast` are counted once.
+    MissingSignatures(Vec<String>),
+    /// Not satisfied in the validity interval, whatever the signatures.
+    Unsatisfiable,
+}
+
+impl Evaluation {
+    pub fn is_satisfied(&self) -> bool {
+        matches!(self, Evaluation::Satisfied)
+    }
+}
+
+impl NativeScript {
+    /// Parse the JSON of a native script, in the `cardano-cli` format.
+    pub fn from_json(json: &JsonValue) -> Result<Self, ParseError> {
+        Self::from_json_nested(json, 0)
+    }
+
+    fn from_json_nested(json: &JsonValue, depth: usize) -> Result<Self, ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(error("native script is nested too deeply"));
+        }
+
+        let field =
+            |name: &str| json.get(name).ok_or_else(|| error(format!("no '{}' field", name)));
+        let number = |name: &str| {
+            field(name)?.as_u64().ok_or_else(|| error(format!("'{}' is not a number", name)))
+        };
+        let scripts = || {
+            field("scripts")?
+                .as_array()
+                .ok_or_else(|| error("'scripts' is not an array"))?
+                .iter()
+                .map(|script| Self::from_json_nested(script, depth + 1))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        match field("type")?.as_str() {
+            Some("sig") => {
+                let key_hash = field("keyHash")?.as_str().filter(|hash| is_key_hash(hash));
+                let key_hash = key_hash.ok_or_else(|| error("'keyHash' is not a 28 bytes hash"))?;
+                Ok(NativeScript::Signature(key_hash.to_lowercase()))
+            }
+            Some("all") => Ok(NativeScript::All(scripts()?)),
+            Some("any") => Ok(NativeScript::Any(scripts()?)),
+            Some("atLeast") => {
+                Ok(NativeScript::AtLeast { required: number("required")?, scripts: scripts()? })
+            }
+            Some("after") => Ok(NativeScript::After(number("slot")?)),
+            Some("before") => Ok(NativeScript::Before(number("slot")?)),
+            _ => Err(error(format!("unknown native script type {}", field("type")?))),
+        }
+    }
+
+    /// The JSON of the script, in the `cardano-cli` format.
+    pub fn to_json(&self) -> JsonValue {
+        let scripts = |scripts: &[NativeScript]| -> Vec<JsonValue> {
+            scripts.iter().map(NativeScript::to_json).collect()
+        };
+
+        match self {
+            NativeScript::Signature(key_hash) => json!({ "type": "sig", "keyHash": key_hash }),
+            NativeScript::All(all) => json!({ "type": "all", "scripts": scripts(all) }),
+            NativeScript::Any(any) => json!({ "type": "any", "scripts": scripts(any) }),
+            NativeScript::AtLeast { required, scripts: at_least } => {
+                json!({ "type": "atLeast", "required": required, "scripts": scripts(at_least) })
+            }
+            NativeScript::After(slot) => json!({ "type": "after", "slot": slot }),
+            NativeScript::Before(slot) => json!({ "type": "before", "slot": slot }),
+        }
+    }
+
+    /// The CBOR of the script, the bytes it's hashed from.
+    ///
+    /// Fails if a [`Signature`](NativeScript::Signature) isn't a hex-encoded key hash.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, ParseError> {
+        let mut encoder = Encoder::new();
+        self.encode(&mut encoder)?;
+        Ok(encoder.into_bytes())
+    }
+
+    /// Hex-encoded hash of the script, its policy ID when it's a minting policy.
+    ///
+    /// Fails like [`to_cbor`](NativeScript::to_cbor).
+    pub fn hash(&self) -> Result<String, ParseError> {
+        Ok(script_hash(ScriptLanguage::Native, &self.to_cbor()?))
+    }
+
+    /// Whether a transaction signed by the `signers` key hashes, in the validity `interval`,
+    /// satisfies the script, and which signatures are still missing if not.
+    ///
+    /// Time locks are checked as the ledger does: `after` needs an `invalid_before` from its slot
+    /// on and `before` needs an `invalid_hereafter` up to its slot.
+    pub fn evaluate(&self, signers: &[impl AsRef<str>], interval: &ValidityInterval) -> Evaluation {
+        let signers: BTreeSet<String> =
+            signers.iter().map(|signer| signer.as_ref().to_lowercase()).collect();
+
+        let alternatives = self.missing_signatures(&signers, interval);
+        // Sorted by size, the first one is among the smallest
+        match alternatives.into_iter().next() {
+            Some(missing) if missing.is_empty() => Evaluation::Satisfied,
+            Some(missing) => Evaluation::MissingSignatures(missing.into_iter().collect()),
+            None => Evaluation::Unsatisfiable,
+        }
+    }
+
+    // Minimal sets of signatures needed besides the signers, sorted by size, none if no
+    // signatures are enough
+    fn missing_signatures(
+        &self,
+        signers: &BTreeSet<String>,
+        interval: &ValidityInterval,
+    ) -> Vec<BTreeSet<String>> {
+        let satisfied = |is_satisfied: bool| {
+            if is_satisfied {
+                vec![BTreeSet::new()]
+            } else {
+                Vec::new()
+            }
+        };
+
+        match self {
+            NativeScript::Signature(key_hash) if signers.contains(key_hash) => satisfied(true),
+            NativeScript::Signature(key_hash) => vec![BTreeSet::from([key_hash.clone()])],
+            NativeScript::All(scripts) => {
+                at_least_missing(scripts, scripts.len(), signers, interval)
+            }
+            NativeScript::Any(scripts) => at_least_missing(scripts, 1, signers, interval),
+            NativeScript::AtLeast { required, scripts } => match usize::try_from(*required) {
+                Ok(required) => at_least_missing(scripts, required, signers, interval),
+                Err(_) => Vec::new(),
+            },
+            NativeScript::After(slot) => {
+                satisfied(interval.invalid_before.is_some_and(|start| start >= *slot))
+            }
+            NativeScript::Before(slot) => {
+                satisfied(interval.invalid_hereafter.is_some_and(|end| end <= *slot))
+            }
+        }
+    }
+
+    // Arrays whose first element is the kind of the node
+    fn encode(&self, encoder: &mut Encoder) -> Result<(), ParseError> {
+        let encode_all = |encoder: &mut Encoder, scripts: &[NativeScript]| {
+            encoder.array(scripts.len());
+            scripts.iter().try_for_each(|script| script.encode(encoder))
+        };
+
+        match self {
+            NativeScript::Signature(key_hash) => {
+                let bytes = decode_hex(key_hash).filter(|bytes| bytes.len() == 28);
+                let bytes =
+                    bytes.ok_or_else(|| error(format!("'{}' is not a key hash", key_hash)))?;
+                encoder.array(2).unsigned(0).bytes(&bytes);
+            }
+            NativeScript::All(scripts) => encode_all(encoder.array(2).unsigned(1), scripts)?,
+            NativeScript::Any(scripts) => encode_all(encoder.array(2).unsigned(2), scripts)?,
+            NativeScript::AtLeast { required, scripts } => {
+                encode_all(encoder.array(3).unsigned(3).unsigned(*required), scripts)?
+            }
+            NativeScript::After(slot) => {
+                encoder.array(2).unsigned(4).unsigned(*slot);
+            }
+            NativeScript::Before(slot) => {
+                encoder.array(2).unsigned(5).unsigned(*slot);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A script whose hash was computed locally and matches the requested one, created by
+/// [`verified_script`](BlockFrostApi::verified_script).
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifiedScript {
+    /// Hex-encoded script hash.
+    pub script_hash: String,
+    pub language: ScriptLanguage,
+    /// Serialized bytes of the script, without the language tag.
+    pub bytes: Vec<u8>,
+    /// JSON of a native script, `None` for Plutus scripts.
+    pub json: Option<JsonValue>,
+}
+
+impl VerifiedScript {
+    /// Parse [`json`](VerifiedScript::json), `None` for Plutus scripts.
+    pub fn native_script(&self) -> Option<Result<NativeScript, ParseError>> {
+        self.json.as_ref().map(NativeScript::from_json)
+    }
+}
+
+impl BlockFrostApi {
+    /// Fetch a script, its JSON if it's native or its CBOR if it's Plutus, and check that it hashes
+    /// to `hash`.
+    ///
+    /// Fails with [`Error::Parse`](crate::Error::Parse) when the returned script doesn't match.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// let hash = "e1457a0c47dfb7a2f6b8fbb059bdceab163c05d34f195b87b9f2b30e";
+    /// let script = api.verified_script(hash).await?;
+    /// println!("{:?} script of {} bytes", script.language, script.bytes.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn verified_script(&self, hash: &str) -> crate::Result<VerifiedScript> {
+        let expected = hash.to_lowercase();
+        let script = self.scripts_by_id(hash).await?;
+
+        let (bytes, json) = match script.type_ {
+            ScriptType::Timelock => {
+                let json = self.scripts_json(hash).await?.json;
+                let json = json.ok_or_else(|| error("native script has no JSON"))?;
+                (NativeScript::from_json(&json)?.to_cbor()?, Some(json))
+            }
+            _ => {
+                let cbor = self.scripts_cbor(hash).await?.cbor;
+                let cbor = cbor.ok_or_else(|| error("Plutus script has no CBOR"))?;
+                (plutus_script_bytes(&cbor)?, None)
+            }
+        };
+
+        let languages = ScriptLanguage::candidates(&script.type_);
+        let language = languages
+            .iter()
+            .copied()
+            .find(|&language| script_hash(language, &bytes) == expected)
+            .ok_or_else(|| {
+                let found = script_hash(languages[0], &bytes);
+                error(format!("hash {} does not match {}", found, expected))
+            })?;
+
+        Ok(VerifiedScript { script_hash: expected, language, bytes, json })
+    }
+}
+
+// Minimal sets of signatures satisfying `required` of the `scripts`, going through them with the
+// alternatives for every number of satisfied scripts so far
+fn at_least_missing(
+    scripts: &[NativeScript],
+    required: usize,
+    signers: &BTreeSet<String>,
+    interval: &ValidityInterval,
+) -> Vec<BTreeSet<String>> {
+    let mut by_satisfied = vec![vec![BTreeSet::new()]];
+    for script in scripts {
+        let alternatives = script.missing_signatures(signers, interval);
+        for satisfied in (0..by_satisfied.len().min(required)).rev() {
+            let mut combined: Vec<BTreeSet<String>> = by_satisfied[satisfied]
+                .iter()
+                .flat_map(|before| alternatives.iter().map(move |missing| before | missing))
+                .collect();
+            match by_satisfied.get_mut(satisfied + 1) {
+                Some(existing) => combined.append(existing),
+                None => by_satisfied.push(Vec::new()),
+            }
+            by_satisfied[satisfied + 1] = minimal_sets(combined);
+        }
+    }
+    by_satisfied.into_iter().nth(required).unwrap_or_default()
+}
+
+// The sets that have no other set as a subset, sorted by size, then in order
+fn minimal_sets(mut sets: Vec<BTreeSet<String>>) -> Vec<BTreeSet<String>> {
+    sets.sort_by(|left, right| left.len().cmp(&right.len()).then_with(|| left.cmp(right)));
+    let mut minimal: Vec<BTreeSet<String>> = Vec::new();
+    for set in sets {
+        if !minimal.iter().any(|smaller| smaller.is_subset(&set)) {
+            minimal.push(set);
+        }
+    }
+    minimal
+}
+
+fn is_key_hash(text: &str) -> bool {
+    decode_hex(text).is_some_and(|bytes| bytes.len() == 28)
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("script", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const KEY_HASH: &str = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+
+    fn native(json: JsonValue) -> NativeScript {
+        NativeScript::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn test_native_script_hash() {
+        let sig = json!({ "type": "sig", "keyHash": KEY_HASH });
+        let script = native(sig.clone());
+        assert_eq!(encode_hex(&script.to_cbor().unwrap()), format!("8200581c{}", KEY_HASH));
+        let hash = "208bdcaf2d83ae026964e23659c703a377473168a39cbdc2b0241115";
+        assert_eq!(script.hash().unwrap(), hash);
+
+        let all = json!({ "type": "all", "scripts": [sig, { "type": "after", "slot": 1000 }] });
+        let script = native(all.clone());
+        let cbor = format!("8201828200581c{}82041903e8", KEY_HASH);
+        assert_eq!(encode_hex(&script.to_cbor().unwrap()), cbor);
+        let hash = "0357bf1acc3716e3cd220042693ddf53ae3d3e365f94e709475006dd";
+        assert_eq!(script.hash().unwrap(), hash);
+        assert_eq!(script.to_json(), all);
+
+        let at_least = native(json!({ "type": "atLeast", "required": 1, "scripts": [] }));
+        assert_eq!(encode_hex(&at_least.to_cbor().unwrap()), "83030180");
+        assert!(NativeScript::from_json(&json!({ "type": "sig", "keyHash": "00" })).is_err());
+        assert!(NativeScript::from_json(&json!({ "type": "never" })).is_err());
+
+        // Built by hand, not parsed
+        let invalid = NativeScript::Any(vec![NativeScript::Signature("not hex".to_owned())]);
+        assert!(invalid.to_cbor().is_err());
+        assert!(invalid.hash().is_err());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let [a, b, c] = ["a", "b", "c"].map(|key| key.repeat(56));
+        let sig = |key: &str| json!({ "type": "sig", "keyHash": key });
+        let treasury = native(json!({
+            "type": "any",
+            "scripts": [
+                { "type": "atLeast", "required": 2, "scripts": [sig(&a), sig(&b), sig(&c)] },
+                { "type": "all", "scripts": [sig(&a), { "type": "after", "slot": 1000 }] },
+            ],
+        }));
+        let before_1000 = ValidityInterval { invalid_before: Some(900), invalid_hereafter: None };
+        let after_1000 = ValidityInterval { invalid_before: Some(1000), invalid_hereafter: None };
+
+        assert_eq!(treasury.evaluate(&[&a, &c], &before_1000), Evaluation::Satisfied);
+        assert_eq!(treasury.evaluate(&[&a], &after_1000), Evaluation::Satisfied);
+        assert_eq!(
+            treasury.evaluate(&[&a], &before_1000),
+            Evaluation::MissingSignatures(vec![b.clone()])
+        );
+        assert_eq!(
+            treasury.evaluate(&[] as &[&str], &before_1000),
+            Evaluation::MissingSignatures(vec![a.clone(), b.clone()])
+        );
+
+        let deadline = native(json!({ "type": "all", "scripts": [
+            sig(&a),
+            { "type": "before", "slot": 2000 },
+        ]}));
+        let open = ValidityInterval::default();
+        let until_2000 = ValidityInterval { invalid_before: None, invalid_hereafter: Some(2000) };
+        assert_eq!(deadline.evaluate(&[&a], &open), Evaluation::Unsatisfiable);
+        assert!(deadline.evaluate(&[a.to_uppercase()], &until_2000).is_satisfied());
+
+        // Branches sharing signatures, the smallest set isn't made of the smallest branches
+        let x = "d".repeat(56);
+        let both = json!({ "type": "all", "scripts": [sig(&a), sig(&b)] });
+        let shared = native(json!({
+            "type": "atLeast",
+            "required": 2,
+            "scripts": [sig(&x), both.clone(), both],
+        }));
+        let missing = shared.evaluate(&[] as &[&str], &open);
+        assert_eq!(missing, Evaluation::MissingSignatures(vec![a.clone(), b.clone()]));
+        let missing = shared.evaluate(&[&x, &a], &open);
+        assert_eq!(missing, Evaluation::MissingSignatures(vec![b.clone()]));
+        let nothing = native(json!({ "type": "any", "scripts": [] }));
+        assert_eq!(nothing.evaluate(&[&a], &open), Evaluation::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_plutus_script_bytes() {
+        // Wrapped twice, as returned by the API, or once
+        let hash = "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656";
+        for cbor in ["4e4d01000033222220051200120011", "4d01000033222220051200120011"] {
+            let bytes = plutus_script_bytes(cbor).unwrap();
+            assert_eq!(encode_hex(&bytes), "4d01000033222220051200120011");
+            assert_eq!(script_hash(ScriptLanguage::PlutusV1, &bytes), hash);
+        }
+
+        assert!(plutus_script_bytes("4d0100").is_err());
+        assert!(plutus_script_bytes("01").is_err());
+    }
+}
@@ -0,0 +1,581 @@
+//! Transaction submission, with local validation and confirmation tracking.
+//!
+//! [`transactions_submit`](BlockFrostApi::transactions_submit) takes a [`TxPayload`], checked
+//! locally before it's sent. [`BlockFrostApi::submit_and_track`] also follows the transaction
+//! until it's final.
+
+use std::{collections::VecDeque, time::Duration};
+
+use futures::stream::{self, Stream};
+use futures_timer::Delay;
+
+use crate::{
+    cbor::{Decoder, Header},
+    transaction::DecodedTransaction,
+    utils::{blake2b_256, decode_hex, encode_hex},
+    BlockFrostApi, BlockHash, Integer, JsonValue, ParseError, TxHash,
+};
+
+/// A transaction to submit, as given by the caller, see [`TxPayload::decode`].
+///
+/// It converts from CBOR bytes, and from text or bytes of text: hex-encoded CBOR, or the
+/// `cardano-cli` TextEnvelope JSON of a signed transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TxPayload {
+    Cbor(Vec<u8>),
+    Text(String),
+}
+
+impl From<Vec<u8>> for TxPayload {
+    fn from(bytes: Vec<u8>) -> Self {
+        // Transactions are CBOR arrays, whose first byte can't start UTF-8 text
+        match String::from_utf8(bytes) {
+            Ok(text) => TxPayload::Text(text),
+            Err(error) => TxPayload::Cbor(error.into_bytes()),
+        }
+    }
+}
+
+impl From<&[u8]> for TxPayload {
+    fn from(bytes: &[u8]) -> Self {
+        bytes.to_vec().into()
+    }
+}
+
+impl From<String> for TxPayload {
+    fn from(text: String) -> Self {
+        TxPayload::Text(text)
+    }
+}
+
+impl From<&str> for TxPayload {
+    fn from(text: &str) -> Self {
+        TxPayload::Text(text.to_owned())
+    }
+}
+
+impl TxPayload {
+    /// Decode the CBOR and check that it's a transaction, an array of its body, witnesses, validity
+    /// flag (since Alonzo) and auxiliary data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blockfrost::submit::TxPayload;
+    ///
+    /// let envelope = r#"{
+    ///     "type": "Witnessed Tx BabbageEra",
+    ///     "description": "Ledger Cddl Format",
+    ///     "cborHex": "84a3008001800200a0f5f6"
+    /// }"#;
+    /// let transaction = TxPayload::from(envelope).decode().unwrap();
+    /// assert_eq!(
+    ///     transaction.hash().as_str(),
+    ///     "36fdff68dfe3660f1ceea60f018a0fd7a83da13def229108794c397a879b0436"
+    /// );
+    /// ```
+    pub fn decode(self) -> Result<SignedTransaction, ParseError> {
+        let bytes = match self {
+            TxPayload::Cbor(bytes) => bytes,
+            TxPayload::Text(text) => decode_text(text.trim())?,
+        };
+        let hash = body_hash(transaction_body(&bytes)?)?;
+        Ok(SignedTransaction { bytes, hash })
+    }
+}
+
+/// A transaction whose CBOR was checked and hash computed locally, created by
+/// [`TxPayload::decode`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedTransaction {
+    bytes: Vec<u8>,
+    hash: TxHash,
+}
+
+impl SignedTransaction {
+    /// The CBOR of the transaction.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Hash of the transaction, the Blake2b-256 digest of its body.
+    pub fn hash(&self) -> &TxHash {
+        &self.hash
+    }
+
+    /// Decode the transaction, to show what's about to be signed or submitted.
+    pub fn decoded(&self) -> Result<DecodedTransaction, ParseError> {
+        DecodedTransaction::from_cbor(&self.bytes)
+    }
+
+    /// Check that the transaction fits within the `max_tx_size` protocol parameter.
+    pub fn check_size(&self, max_tx_size: i64) -> Result<(), ParseError> {
+        if i64::try_from(self.bytes.len()).map_or(true, |size| size > max_tx_size) {
+            return Err(error(format!(
+                "size of {} bytes is over the maximum of {} bytes",
+                self.bytes.len(),
+                max_tx_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the hash returned by the server against the local one.
+    pub fn check_hash(&self, hash: &str) -> Result<(), ParseError> {
+        if !hash.eq_ignore_ascii_case(&self.hash) {
+            return Err(error(format!("server returned hash {}, expected {}", hash, self.hash)));
+        }
+        Ok(())
+    }
+}
+
+// Polling interval of a tracker, the average time between blocks
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(20);
+
+// Blocks to wait for a transaction missing from the mempool to be indexed, before it's dropped
+const DROPPED_AFTER_BLOCKS: Integer = 3;
+
+/// State of a tracked transaction, yielded by [`TxTracker::statuses`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Submitted, but not seen in the mempool or in a block yet.
+    Submitted,
+    /// Waiting in the mempool.
+    InMempool,
+    /// Included in a block, yielded again with the new block after a rollback.
+    Included { block: BlockHash, height: Integer },
+    /// Number of blocks on top of the block including the transaction.
+    Confirmed(Integer),
+    /// Deeper than the `security_param` of the [`genesis`](BlockFrostApi::genesis), it can't be
+    /// rolled back.
+    Final,
+    /// Left the mempool, or never showed up in it, without being included, e.g. its inputs were
+    /// spent by another transaction.
+    Dropped,
+    /// Not included before the tip passed its `invalid_hereafter` slot.
+    Expired,
+}
+
+impl TxStatus {
+    /// Whether the status won't change anymore, the last one of [`TxTracker::statuses`].
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, TxStatus::Final | TxStatus::Dropped | TxStatus::Expired)
+    }
+}
+
+/// A submitted transaction, created by [`submit_and_track`](BlockFrostApi::submit_and_track) or
+/// [`track_transaction`](BlockFrostApi::track_transaction).
+#[derive(Clone, Debug)]
+pub struct TxTracker<'a> {
+    api: &'a BlockFrostApi,
+    hash: String,
+    interval: Duration,
+    invalid_hereafter: Option<Integer>,
+}
+
+impl<'a> TxTracker<'a> {
+    /// Hash of the tracked transaction.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Poll every `interval`, instead of every 20 seconds.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The `invalid_hereafter` slot of the transaction, so it can expire before it's seen in the
+    /// mempool, which tells it otherwise. [`submit_and_track`](BlockFrostApi::submit_and_track)
+    /// sets it from the transaction.
+    pub fn with_invalid_hereafter(mut self, slot: Integer) -> Self {
+        self.invalid_hereafter = Some(slot);
+        self
+    }
+
+    /// Stream the states of the transaction, starting with [`TxStatus::Submitted`] and ending with
+    /// a terminal one.
+    ///
+    /// Every poll requests [`blocks_latest`](BlockFrostApi::blocks_latest),
+    /// [`transaction_by_hash`](BlockFrostApi::transaction_by_hash) and, until the transaction is
+    /// in a block, [`mempool_by_hash`](BlockFrostApi::mempool_by_hash). Only changes are yielded.
+    /// Failed requests are yielded and the stream goes on.
+    pub fn statuses(self) -> impl Stream<Item = crate::Result<TxStatus>> + 'a {
+        let tracking = Tracking {
+            invalid_hereafter: self.invalid_hereafter,
+            last: Some(TxStatus::Submitted),
+            ..Tracking::default()
+        };
+        let pending = VecDeque::from([TxStatus::Submitted]);
+
+        stream::unfold(
+            (self, tracking, pending, None, true),
+            |(tracker, mut tracking, mut pending, mut security_param, mut is_first)| async move {
+                loop {
+                    if let Some(status) = pending.pop_front() {
+                        if status.is_terminal() {
+                            pending.clear();
+                            tracking.is_finished = true;
+                        }
+                        let state = (tracker, tracking, pending, security_param, is_first);
+                        return Some((Ok(status), state));
+                    }
+                    if tracking.is_finished {
+                        return None;
+                    }
+
+                    if !is_first {
+                        Delay::new(tracker.interval).await;
+                    }
+                    is_first = false;
+
+                    match tracker.observe(&mut security_param).await {
+                        Ok(observation) => pending.extend(tracking.update(&observation)),
+                        Err(error) => {
+                            let state = (tracker, tracking, pending, security_param, is_first);
+                            return Some((Err(error), state));
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    async fn observe(&self, security_param: &mut Option<Integer>) -> crate::Result<Observation> {
+        let security_param = match *security_param {
+            Some(security_param) => security_param,
+            None => *security_param.insert(self.api.genesis().await?.security_param),
+        };
+        let tip = self.api.blocks_latest().await?;
+
+        let block = match self.api.transaction_by_hash(&self.hash).await {
+            Ok(transaction) => Some((transaction.block, transaction.block_height)),
+            Err(error) if error.is_not_found() => None,
+            Err(error) => return Err(error),
+        };
+        let (in_mempool, invalid_hereafter) = match block {
+            Some(_) => (false, None),
+            None => match self.api.mempool_by_hash(&self.hash).await {
+                Ok(transaction) => {
+                    (true, transaction.tx.invalid_hereafter.and_then(|slot| slot.parse().ok()))
+                }
+                Err(error) if error.is_not_found() => (false, None),
+                Err(error) => return Err(error),
+            },
+        };
+
+        Ok(Observation {
+            security_param,
+            tip_height: tip.height.unwrap_or_default(),
+            tip_slot: tip.slot.unwrap_or_default(),
+            block,
+            in_mempool,
+            invalid_hereafter,
+        })
+    }
+}
+
+impl BlockFrostApi {
+    /// Submit a transaction with [`transactions_submit`](BlockFrostApi::transactions_submit), and
+    /// track it until it's final, dropped or expired.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi, cbor: Vec<u8>) -> blockfrost::Result<()> {
+    /// use blockfrost::stream::StreamExt;
+    ///
+    /// let tracker = api.submit_and_track(cbor).await?;
+    /// println!("submitted {}", tracker.hash());
+    ///
+    /// let mut statuses = Box::pin(tracker.statuses());
+    /// while let Some(status) = statuses.next().await {
+    ///     println!("{:?}", status?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn submit_and_track(
+        &self,
+        transaction: impl Into<TxPayload>,
+    ) -> crate::Result<TxTracker<'_>> {
+        let transaction = transaction.into().decode()?;
+        // The body may use parts the local decoder doesn't know, tracking works without it
+        let invalid_hereafter = transaction.decoded().ok().and_then(|tx| tx.invalid_hereafter);
+
+        let hash = self.submit_signed(transaction).await?;
+        let tracker = self.track_transaction(&hash);
+        Ok(match invalid_hereafter {
+            Some(slot) => tracker.with_invalid_hereafter(slot.into()),
+            None => tracker,
+        })
+    }
+
+    /// Track a transaction submitted earlier, see
+    /// [`submit_and_track`](BlockFrostApi::submit_and_track).
+    pub fn track_transaction(&self, hash: &str) -> TxTracker<'_> {
+        TxTracker {
+            api: self,
+            hash: hash.to_owned(),
+            interval: DEFAULT_INTERVAL,
+            invalid_hereafter: None,
+        }
+    }
+}
+
+// What a poll found out about the transaction
+#[derive(Clone, Debug, Default)]
+struct Observation {
+    security_param: Integer,
+    tip_height: Integer,
+    tip_slot: Integer,
+    block: Option<(BlockHash, Integer)>,
+    in_mempool: bool,
+    invalid_hereafter: Option<Integer>,
+}
+
+// What previous polls found out about the transaction
+#[derive(Clone, Debug, Default)]
+struct Tracking {
+    last: Option<TxStatus>,
+    included: Option<(BlockHash, Integer)>,
+    // Tip height when the transaction was first missing from the mempool and the chain
+    left_mempool_at: Option<Integer>,
+    invalid_hereafter: Option<Integer>,
+    is_finished: bool,
+}
+
+impl Tracking {
+    // New statuses, in order
+    fn update(&mut self, observation: &Observation) -> Vec<TxStatus> {
+        if observation.invalid_hereafter.is_some() {
+            self.invalid_hereafter = observation.invalid_hereafter;
+        }
+
+        let mut statuses = Vec::new();
+        if let Some((block, height)) = &observation.block {
+            self.left_mempool_at = None;
+            if self.included.as_ref() != observation.block.as_ref() {
+                self.included = observation.block.clone();
+                statuses.push(TxStatus::Included { block: block.clone(), height: *height });
+            }
+            let confirmations = observation.tip_height - height;
+            if confirmations >= observation.security_param {
+                statuses.push(TxStatus::Final);
+            } else if confirmations > 0 {
+                statuses.push(TxStatus::Confirmed(confirmations));
+            }
+        } else {
+            // Not in a block, or rolled back
+            self.included = None;
+            if observation.in_mempool {
+                self.left_mempool_at = None;
+                statuses.push(TxStatus::InMempool);
+            } else if self.invalid_hereafter.is_some_and(|slot| observation.tip_slot >= slot) {
+                statuses.push(TxStatus::Expired);
+            } else {
+                let left_at = *self.left_mempool_at.get_or_insert(observation.tip_height);
+                if observation.tip_height >= left_at + DROPPED_AFTER_BLOCKS {
+                    statuses.push(TxStatus::Dropped);
+                }
+            }
+        }
+
+        statuses.retain(|status| {
+            let is_new = self.last.as_ref() != Some(status);
+            if is_new {
+                self.last = Some(status.clone());
+            }
+            is_new
+        });
+        statuses
+    }
+}
+
+// Hex-encoded CBOR, or a TextEnvelope
+fn decode_text(text: &str) -> Result<Vec<u8>, ParseError> {
+    if !text.starts_with('{') {
+        return decode_hex(text).ok_or_else(|| error("text is neither hex nor JSON"));
+    }
+
+    let envelope: JsonValue =
+        serde_json::from_str(text).map_err(|_| error("TextEnvelope is not JSON"))?;
+    match envelope.get("type").and_then(JsonValue::as_str) {
+        Some(type_) if type_.contains("Tx") && !type_.contains("TxBody") => {}
+        Some(type_) => return Err(error(format!("TextEnvelope of type '{}'", type_))),
+        None => return Err(error("TextEnvelope has no 'type'")),
+    }
+    let cbor_hex = envelope.get("cborHex").and_then(JsonValue::as_str);
+    let cbor_hex = cbor_hex.ok_or_else(|| error("TextEnvelope has no 'cborHex'"))?;
+    decode_hex(cbor_hex).ok_or_else(|| error("'cborHex' is not hex"))
+}
+
+// The encoded body of a transaction, the bytes its hash is computed from
+pub(crate) fn transaction_body(bytes: &[u8]) -> Result<&[u8], ParseError> {
+    let mut decoder = Decoder::new(bytes);
+    let cbor_error = |cbor_error: ParseError| error(cbor_error.reason);
+
+    let length = match decoder.header().map_err(cbor_error)? {
+        Header::Array(Some(length @ (3 | 4))) => length,
+        _ => return Err(error("not an array of 3 or 4 items")),
+    };
+
+    let start = decoder.position();
+    if !matches!(decoder.peek_header().map_err(cbor_error)?, Header::Map(_)) {
+        return Err(error("body is not a map"));
+    }
+    decoder.skip().map_err(cbor_error)?;
+    let end = decoder.position();
+
+    if !matches!(decoder.peek_header().map_err(cbor_error)?, Header::Map(_)) {
+        return Err(error("witness set is not a map"));
+    }
+    decoder.skip().map_err(cbor_error)?;
+    if length == 4 && !matches!(decoder.header().map_err(cbor_error)?, Header::Simple(20 | 21)) {
+        return Err(error("validity flag is not a boolean"));
+    }
+    decoder.skip().map_err(cbor_error)?;
+
+    if !decoder.is_finished() {
+        return Err(error("trailing bytes after the transaction"));
+    }
+    Ok(&bytes[start..end])
+}
+
+// Blake2b-256 digest of the encoded body
+pub(crate) fn body_hash(body: &[u8]) -> Result<TxHash, ParseError> {
+    encode_hex(&blake2b_256(body)).parse()
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("transaction", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // [{0: [], 1: [], 2: 0}, {}, true, null]
+    const TRANSACTION: &str = "84a3008001800200a0f5f6";
+    const HASH: &str = "36fdff68dfe3660f1ceea60f018a0fd7a83da13def229108794c397a879b0436";
+
+    const BLOCK: &str = "4ea1ba291e8eef538635a53e59fddba7810d1679631cc3aed7c8e6c4091a516a";
+
+    fn observe(
+        tracking: &mut Tracking,
+        tip_height: Integer,
+        block: Option<Integer>,
+    ) -> Vec<TxStatus> {
+        tracking.update(&Observation {
+            security_param: 10,
+            tip_height,
+            tip_slot: tip_height * 20,
+            block: block.map(|height| (BLOCK.parse().unwrap(), height)),
+            in_mempool: false,
+            invalid_hereafter: None,
+        })
+    }
+
+    #[test]
+    fn test_included_to_final() {
+        let mut tracking = Tracking { last: Some(TxStatus::Submitted), ..Tracking::default() };
+        let included = TxStatus::Included { block: BLOCK.parse().unwrap(), height: 100 };
+
+        let in_mempool =
+            Observation { in_mempool: true, security_param: 10, ..Observation::default() };
+        assert_eq!(tracking.update(&in_mempool), [TxStatus::InMempool]);
+        assert_eq!(tracking.update(&in_mempool), []);
+
+        assert_eq!(observe(&mut tracking, 100, Some(100)), vec![included.clone()]);
+        assert_eq!(observe(&mut tracking, 100, Some(100)), []);
+        assert_eq!(observe(&mut tracking, 102, Some(100)), [TxStatus::Confirmed(2)]);
+
+        // Rolled back, then included again in the same block
+        assert_eq!(observe(&mut tracking, 101, None), []);
+        assert_eq!(observe(&mut tracking, 103, Some(100)), [included, TxStatus::Confirmed(3)]);
+        assert_eq!(observe(&mut tracking, 110, Some(100)), [TxStatus::Final]);
+    }
+
+    #[test]
+    fn test_dropped_and_expired() {
+        let mut tracking = Tracking::default();
+        let in_mempool = Observation {
+            security_param: 10,
+            tip_height: 100,
+            in_mempool: true,
+            invalid_hereafter: Some(2500),
+            ..Observation::default()
+        };
+        assert_eq!(tracking.update(&in_mempool), [TxStatus::InMempool]);
+
+        // Left the mempool, but may not be indexed yet
+        let mut dropped = tracking.clone();
+        assert_eq!(observe(&mut dropped, 101, None), []);
+        assert_eq!(observe(&mut dropped, 104, None), [TxStatus::Dropped]);
+
+        // Missing for one poll, then included and rolled back much later
+        let mut rolled_back = tracking.clone();
+        assert_eq!(observe(&mut rolled_back, 101, None), []);
+        assert_eq!(observe(&mut rolled_back, 102, Some(102)).len(), 1);
+        assert_eq!(observe(&mut rolled_back, 110, None), []);
+        assert_eq!(observe(&mut rolled_back, 113, None), [TxStatus::Dropped]);
+
+        // Never seen in the mempool
+        let mut submitted = Tracking { last: Some(TxStatus::Submitted), ..Tracking::default() };
+        assert_eq!(observe(&mut submitted, 100, None), []);
+        assert_eq!(observe(&mut submitted, 103, None), [TxStatus::Dropped]);
+
+        // Slot 2500 is the tip of height 125
+        assert_eq!(observe(&mut tracking, 125, None), [TxStatus::Expired]);
+    }
+
+    #[test]
+    fn test_decode_payload() {
+        let bytes = decode_hex(TRANSACTION).unwrap();
+        let envelope = format!(r#"{{"type": "Tx AlonzoEra", "cborHex": "{}"}}"#, TRANSACTION);
+        let payloads = [
+            TxPayload::from(bytes.clone()),
+            TxPayload::from(TRANSACTION),
+            TxPayload::from(format!("{}\n", TRANSACTION).into_bytes()),
+            TxPayload::from(envelope.as_bytes()),
+        ];
+        for payload in payloads {
+            let transaction = payload.decode().unwrap();
+            assert_eq!(transaction.hash(), HASH);
+            assert_eq!(transaction.as_bytes(), bytes);
+        }
+
+        // Mary transactions have no validity flag
+        assert_eq!(TxPayload::from("83a3008001800200a0f6").decode().unwrap().hash(), HASH);
+
+        let invalid = [
+            "a3008001800200",
+            "84a3008001800200a0f5",
+            "84a3008001800200a0f5f600",
+            "84a3008001800200a000f6",
+            "84a300800180020080f5f6",
+            "not hex",
+            r#"{"type": "TxBodyBabbage", "cborHex": "a3008001800200"}"#,
+        ];
+        for payload in invalid {
+            assert!(TxPayload::from(payload).decode().is_err(), "{}", payload);
+        }
+
+        // Deeply nested auxiliary data must fail, not overflow the stack
+        let nested = format!("84a0a0f5{}00", "81".repeat(200_000));
+        assert!(TxPayload::from(nested).decode().is_err());
+    }
+
+    #[test]
+    fn test_checks() {
+        let transaction = TxPayload::from(TRANSACTION).decode().unwrap();
+        assert!(transaction.check_size(11).is_ok());
+        assert!(transaction.check_size(10).is_err());
+        assert!(transaction.check_hash(&HASH.to_uppercase()).is_ok());
+        assert!(transaction.check_hash(BLOCK).is_err());
+    }
+}
@@ -0,0 +1,471 @@
+//! Conversions between slots, UNIX times and epochs, see [`TimeConverter`].
+
+use crate::{Block, BlockFrostApi, Error, Genesis, Integer, ParseError};
+
+// Blocks between the search bounds below which the next page is listed instead of bisecting
+const PAGE_GAP: Integer = 100;
+
+// Blocks between the estimated height and the next probe, doubled until the time is bracketed
+const BRACKET_STEP: Integer = 50;
+
+// Requests after which `block_at_time` gives up
+const MAX_REQUESTS: u32 = 64;
+
+/// Start of an era with a fixed slot length and epoch length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EraSummary {
+    /// First slot of the era.
+    pub start_slot: u64,
+    /// UNIX time of the first slot, in seconds.
+    pub start_time: u64,
+    /// First epoch of the era.
+    pub start_epoch: u64,
+    /// Duration of one slot in seconds.
+    pub slot_length: u64,
+    /// Number of slots in an epoch.
+    pub epoch_length: u64,
+}
+
+/// An epoch and the slot relative to its start, `epoch_slot` in the API responses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochSlot {
+    pub epoch: u64,
+    pub epoch_slot: u64,
+}
+
+/// Converts between absolute slots, UNIX times (in seconds) and epochs, offline.
+///
+/// Slots lasted 20 seconds in the Byron era and 1 second since Shelley, so conversions need the
+/// history of eras. [`TimeConverter::from_genesis`] knows the Byron era of the public networks.
+///
+/// # Example
+///
+/// Computing a validity interval ending in one hour:
+///
+/// ```no_run
+/// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+/// use std::time::{SystemTime, UNIX_EPOCH};
+///
+/// let converter = api.time_converter().await?;
+///
+/// let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+/// let invalid_before = converter.time_to_slot(now).unwrap();
+/// let invalid_hereafter = converter.time_to_slot(now + 3600).unwrap();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeConverter {
+    eras: Vec<EraSummary>,
+}
+
+impl TimeConverter {
+    /// Byron slot length in seconds, the same on every public network.
+    pub const BYRON_SLOT_LENGTH: u64 = 20;
+
+    /// Create from the eras of a network, sorted by start.
+    ///
+    /// Fails if there's no era, if the first one doesn't start at slot and epoch 0, or if an era
+    /// doesn't start at an epoch boundary of the previous one.
+    pub fn new(eras: Vec<EraSummary>) -> Result<Self, ParseError> {
+        let error = |reason: &str| Err(ParseError::new("era history", reason));
+
+        match eras.first() {
+            None => return error("no eras"),
+            Some(first) if first.start_slot != 0 || first.start_epoch != 0 => {
+                return error("the first era must start at slot and epoch 0");
+            }
+            Some(_) => {}
+        }
+        if eras.iter().any(|era| era.slot_length == 0 || era.epoch_length == 0) {
+            return error("slot and epoch lengths must be positive");
+        }
+        for pair in eras.windows(2) {
+            let (previous, era) = (pair[0], pair[1]);
+            let slots = match era.start_slot.checked_sub(previous.start_slot) {
+                Some(slots) if slots % previous.epoch_length == 0 => slots,
+                _ => return error("eras must start at an epoch boundary of the previous era"),
+            };
+            let expected_time = previous.start_time + slots * previous.slot_length;
+            let expected_epoch = previous.start_epoch + slots / previous.epoch_length;
+            if era.start_time != expected_time || era.start_epoch != expected_epoch {
+                return error("era start doesn't match the end of the previous era");
+            }
+        }
+        Ok(Self { eras })
+    }
+
+    /// Create from the genesis of a network.
+    ///
+    /// The start of Shelley isn't part of the genesis, it's known for the mainnet, preprod and the
+    /// legacy testnet by their network magic. Other networks are assumed to have no Byron era,
+    /// like preview, use [`TimeConverter::new`] for them.
+    pub fn from_genesis(genesis: &Genesis) -> Result<Self, ParseError> {
+        let field = |value, name| {
+            u64::try_from(value)
+                .map_err(|_| ParseError::new("genesis", format!("'{}' is out of range", name)))
+        };
+        let system_start = field(genesis.system_start, "system_start")?;
+        let slot_length = field(genesis.slot_length, "slot_length")?;
+        let epoch_length = field(genesis.epoch_length, "epoch_length")?;
+        let security_param = field(genesis.security_param, "security_param")?;
+
+        let byron_epochs = match genesis.network_magic {
+            764824073 => 208,
+            1097911063 => 74,
+            1 => 4,
+            _ => 0,
+        };
+
+        let shelley = |start_slot, start_time| EraSummary {
+            start_slot,
+            start_time,
+            start_epoch: byron_epochs,
+            slot_length,
+            epoch_length,
+        };
+
+        let eras = if byron_epochs == 0 {
+            vec![shelley(0, system_start)]
+        } else {
+            // Byron epochs have 10k slots
+            let byron = EraSummary {
+                start_slot: 0,
+                start_time: system_start,
+                start_epoch: 0,
+                slot_length: Self::BYRON_SLOT_LENGTH,
+                epoch_length: 10 * security_param,
+            };
+            let shelley_slot = byron_epochs * byron.epoch_length;
+            vec![byron, shelley(shelley_slot, system_start + shelley_slot * byron.slot_length)]
+        };
+        Self::new(eras)
+    }
+
+    /// The eras, sorted by start.
+    pub fn eras(&self) -> &[EraSummary] {
+        &self.eras
+    }
+
+    // Last era matching the predicate, the first one always matches slots and epochs
+    fn era_where(&self, predicate: impl Fn(&EraSummary) -> bool) -> Option<&EraSummary> {
+        self.eras.iter().rev().find(|era| predicate(era))
+    }
+
+    /// UNIX time of the start of a slot.
+    pub fn slot_to_time(&self, slot: u64) -> u64 {
+        let era = self.era_where(|era| era.start_slot <= slot).unwrap_or(&self.eras[0]);
+        era.start_time + (slot - era.start_slot) * era.slot_length
+    }
+
+    /// Slot containing a UNIX time, `None` if it's before the start of the network.
+    pub fn time_to_slot(&self, time: u64) -> Option<u64> {
+        let era = self.era_where(|era| era.start_time <= time)?;
+        Some(era.start_slot + (time - era.start_time) / era.slot_length)
+    }
+
+    /// Epoch of a slot, and the slot relative to the start of that epoch.
+    pub fn slot_to_epoch(&self, slot: u64) -> EpochSlot {
+        let era = self.era_where(|era| era.start_slot <= slot).unwrap_or(&self.eras[0]);
+        let slots = slot - era.start_slot;
+        EpochSlot {
+            epoch: era.start_epoch + slots / era.epoch_length,
+            epoch_slot: slots % era.epoch_length,
+        }
+    }
+
+    /// Absolute slot of an epoch and a slot relative to its start.
+    pub fn epoch_to_slot(&self, epoch_slot: EpochSlot) -> u64 {
+        let era =
+            self.era_where(|era| era.start_epoch <= epoch_slot.epoch).unwrap_or(&self.eras[0]);
+        era.start_slot
+            + (epoch_slot.epoch - era.start_epoch) * era.epoch_length
+            + epoch_slot.epoch_slot
+    }
+
+    /// First slot of an epoch.
+    pub fn epoch_start_slot(&self, epoch: u64) -> u64 {
+        self.epoch_to_slot(EpochSlot { epoch, epoch_slot: 0 })
+    }
+
+    /// UNIX time of the start of an epoch.
+    pub fn epoch_start_time(&self, epoch: u64) -> u64 {
+        self.slot_to_time(self.epoch_start_slot(epoch))
+    }
+
+    /// Epoch containing a UNIX time, `None` if it's before the start of the network.
+    pub fn time_to_epoch(&self, time: u64) -> Option<EpochSlot> {
+        self.time_to_slot(time).map(|slot| self.slot_to_epoch(slot))
+    }
+}
+
+impl BlockFrostApi {
+    /// Build a [`TimeConverter`] from the [`genesis`](BlockFrostApi::genesis) of the network.
+    ///
+    /// See [`TimeConverter::from_genesis`].
+    pub async fn time_converter(&self) -> crate::Result<TimeConverter> {
+        let genesis = self.genesis().await?;
+        Ok(TimeConverter::from_genesis(&genesis)?)
+    }
+
+    /// The first block created at or after a UNIX time, `None` if the time is after the latest
+    /// block.
+    ///
+    /// Slots without blocks make a direct lookup impossible, this first tries the block in the
+    /// first slot starting at `time` with [`blocks_slot`](BlockFrostApi::blocks_slot). Most slots
+    /// are empty, so probing other slots would mostly find nothing, while every height has a
+    /// block. The search goes on with [`blocks_by_id`](BlockFrostApi::blocks_by_id) around the
+    /// height estimated from the average time between blocks, with growing steps until `time` is
+    /// bracketed, narrows the bracket alternating interpolation and bisection, and finishes with
+    /// one page of [`blocks_next`](BlockFrostApi::blocks_next).
+    ///
+    /// Fails with [`Error::RequestLimit`](crate::Error::RequestLimit) if the block isn't found
+    /// within 64 requests.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// // 2024-01-01T00:00:00Z
+    /// if let Some(block) = api.block_at_time(1704067200).await? {
+    ///     println!("first block of 2024: {:?}", block.height);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn block_at_time(&self, time: u64) -> crate::Result<Option<Block>> {
+        let target = Integer::from(time);
+        let latest = self.blocks_latest().await?;
+        if latest.time < target {
+            return Ok(None);
+        }
+
+        let genesis = self.genesis().await?;
+        let converter = TimeConverter::from_genesis(&genesis)?;
+
+        let slot = match converter.time_to_slot(time) {
+            Some(slot) if converter.slot_to_time(slot) < time => slot + 1,
+            Some(slot) => slot,
+            None => 0,
+        };
+        match self.blocks_slot(slot.into()).await {
+            Ok(block) => return Ok(Some(block)),
+            Err(error) if error.is_not_found() => {}
+            Err(error) => return Err(error),
+        }
+
+        let mut search = BlockSearch {
+            api: self,
+            target,
+            start: (0, Integer::from(converter.slot_to_time(0)) - 1),
+            lower: None,
+            upper: latest,
+            requests: 3,
+        };
+
+        // Probe away from the estimate, in the direction of `time`, until a probe lands past it
+        let seconds_per_block = (genesis.slot_length as f64 / genesis.active_slots_coefficient)
+            .round()
+            .max(1.0) as Integer;
+        let (_, upper) = search.bounds()?;
+        let mut height = upper.0 - (upper.1 - target) / seconds_per_block;
+        let mut step = BRACKET_STEP;
+        let mut direction = None;
+        loop {
+            let (lower, upper) = search.bounds()?;
+            if upper.0 - lower.0 <= 1 {
+                return Ok(Some(search.upper));
+            }
+            let is_upper = search.probe(height.clamp(lower.0 + 1, upper.0 - 1)).await?;
+            if direction.is_some_and(|previous| previous != is_upper) {
+                break;
+            }
+            direction = Some(is_upper);
+            height = if is_upper { height - step } else { height + step };
+            step *= 2;
+        }
+
+        let mut round = 0;
+        loop {
+            let (lower, upper) = search.bounds()?;
+            if upper.0 - lower.0 <= 1 {
+                return Ok(Some(search.upper));
+            }
+
+            let near_lower = search.lower.as_ref().filter(|_| upper.0 - lower.0 <= PAGE_GAP);
+            if let Some(hash) = near_lower.map(|block| block.hash.to_string()) {
+                if let Some(found) = search.next_page(&hash).await? {
+                    return Ok(Some(found));
+                }
+                continue;
+            }
+
+            search.probe(next_probe(lower, upper, target, round)).await?;
+            round += 1;
+        }
+    }
+}
+
+// `(height, time)` of a block bounding the search
+type Bound = (Integer, Integer);
+
+// Bounds of the search for the first block at or after `target`, `lower.time < target <=
+// upper.time`, with no lower block known at first
+struct BlockSearch<'a> {
+    api: &'a BlockFrostApi,
+    target: Integer,
+    // Below the first block, the lower bound until one is known
+    start: Bound,
+    lower: Option<Block>,
+    upper: Block,
+    requests: u32,
+}
+
+impl BlockSearch<'_> {
+    fn bounds(&self) -> Result<(Bound, Bound), ParseError> {
+        let lower = match &self.lower {
+            Some(block) => (block_height(block)?, block.time),
+            None => self.start,
+        };
+        Ok((lower, (block_height(&self.upper)?, self.upper.time)))
+    }
+
+    // Fetch the block at a height between the bounds and narrow them, whether it's the new upper
+    async fn probe(&mut self, height: Integer) -> crate::Result<bool> {
+        self.count_request()?;
+        let block = self.api.blocks_by_id(&height.to_string()).await?;
+        let is_upper = block.time >= self.target;
+        if is_upper {
+            self.upper = block;
+        } else {
+            self.lower = Some(block);
+        }
+        Ok(is_upper)
+    }
+
+    // List the blocks after the lower bound, the first one at or after `target` if it's there
+    async fn next_page(&mut self, lower_hash: &str) -> crate::Result<Option<Block>> {
+        self.count_request()?;
+        let mut page = self.api.blocks_next(lower_hash).await?;
+        if let Some(index) = page.iter().position(|block| block.time >= self.target) {
+            return Ok(Some(page.swap_remove(index)));
+        }
+        match page.pop() {
+            Some(last) => self.lower = Some(last),
+            None => return Ok(Some(self.upper.clone())),
+        }
+        Ok(None)
+    }
+
+    fn count_request(&mut self) -> crate::Result<()> {
+        self.requests += 1;
+        if self.requests > MAX_REQUESTS {
+            return Err(Error::RequestLimit { limit: MAX_REQUESTS });
+        }
+        Ok(())
+    }
+}
+
+fn block_height(block: &Block) -> Result<Integer, ParseError> {
+    block.height.ok_or_else(|| ParseError::new("block", format!("'{}' has no height", block.hash)))
+}
+
+// Height to probe strictly between the bounds. Interpolation and bisection alternate, bisection
+// bounds the number of rounds when block times are irregular.
+fn next_probe(lower: Bound, upper: Bound, time: Integer, round: u32) -> Integer {
+    let estimate = match round % 2 {
+        0 => lower.0 + (upper.0 - lower.0) * (time - lower.1) / (upper.1 - lower.1).max(1),
+        _ => lower.0 + (upper.0 - lower.0) / 2,
+    };
+    estimate.clamp(lower.0 + 1, upper.0 - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genesis(network_magic: i128, system_start: i128, epoch_length: i128) -> Genesis {
+        Genesis {
+            active_slots_coefficient: 0.05,
+            update_quorum: 5,
+            max_lovelace_supply: "45000000000000000".to_owned(),
+            network_magic,
+            epoch_length,
+            system_start,
+            slots_per_kes_period: 129600,
+            slot_length: 1,
+            max_kes_evolutions: 62,
+            security_param: 2160,
+        }
+    }
+
+    #[test]
+    fn test_mainnet() {
+        let converter =
+            TimeConverter::from_genesis(&genesis(764824073, 1506203091, 432000)).unwrap();
+
+        // First Shelley block
+        assert_eq!(converter.slot_to_time(4492800), 1596059091);
+        assert_eq!(converter.time_to_slot(1596059091), Some(4492800));
+        assert_eq!(converter.slot_to_epoch(4492800), EpochSlot { epoch: 208, epoch_slot: 0 });
+
+        // Byron slots last 20 seconds
+        assert_eq!(converter.slot_to_time(1), 1506203111);
+        assert_eq!(converter.time_to_slot(1506203110), Some(0));
+        assert_eq!(converter.slot_to_epoch(21601), EpochSlot { epoch: 1, epoch_slot: 1 });
+        assert_eq!(converter.time_to_slot(1506203090), None);
+
+        let slot = 84232747;
+        assert_eq!(converter.slot_to_time(slot), 1675799038);
+        let epoch_slot = converter.slot_to_epoch(slot);
+        assert_eq!(epoch_slot, EpochSlot { epoch: 392, epoch_slot: 251947 });
+        assert_eq!(converter.epoch_to_slot(epoch_slot), slot);
+        assert_eq!(converter.epoch_start_time(209), 1596491091);
+    }
+
+    #[test]
+    fn test_testnets() {
+        let preprod = TimeConverter::from_genesis(&genesis(1, 1654041600, 432000)).unwrap();
+        assert_eq!(preprod.epoch_start_slot(4), 86400);
+        assert_eq!(preprod.epoch_start_time(4), 1655769600);
+
+        let preview = TimeConverter::from_genesis(&genesis(2, 1666656000, 86400)).unwrap();
+        assert_eq!(preview.eras().len(), 1);
+        assert_eq!(preview.time_to_epoch(1666656000 + 86400 * 3 + 5).unwrap().epoch, 3);
+    }
+
+    #[test]
+    fn test_next_probe() {
+        let (lower, upper) = ((0, 1000), (101, 3000));
+
+        // Interpolation, then bisection
+        assert_eq!(next_probe(lower, upper, 2800, 0), 90);
+        assert_eq!(next_probe(lower, upper, 2800, 1), 50);
+        assert_eq!(next_probe(lower, upper, 2800, 2), 90);
+
+        // Always strictly between the bounds
+        assert_eq!(next_probe(lower, upper, 0, 0), 1);
+        assert_eq!(next_probe(lower, upper, 2999, 0), 100);
+        assert_eq!(next_probe((5, 1000), (7, 1000), 1000, 0), 6);
+    }
+
+    #[test]
+    fn test_invalid_eras() {
+        assert!(TimeConverter::new(vec![]).is_err());
+
+        let byron = EraSummary {
+            start_slot: 0,
+            start_time: 0,
+            start_epoch: 0,
+            slot_length: 20,
+            epoch_length: 100,
+        };
+        assert!(TimeConverter::new(vec![EraSummary { start_slot: 1, ..byron }]).is_err());
+
+        let misaligned = EraSummary { start_slot: 150, start_time: 3000, start_epoch: 1, ..byron };
+        assert!(TimeConverter::new(vec![byron, misaligned]).is_err());
+
+        let aligned = EraSummary { start_slot: 200, start_time: 4000, start_epoch: 2, ..byron };
+        assert!(TimeConverter::new(vec![byron, aligned]).is_ok());
+    }
+}
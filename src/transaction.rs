@@ -0,0 +1,490 @@
+//! Local decoding of transactions, to show what a transaction does before it's signed or after
+//! it's fetched with [`transactions_cbor`](crate::BlockFrostApi::transactions_cbor).
+//!
+//! Only the body is decoded, into a [`DecodedTransaction`]. Outputs are [`TxOutput`]s, so their
+//! minimum lovelaces can be checked too.
+
+use std::fmt;
+
+use crate::{
+    address::{Credential, DecodedAddress},
+    cbor::{self, Decoder, Header},
+    min_utxo::{OutputDatum, TxOutput},
+    script::ValidityInterval,
+    submit::{body_hash, transaction_body},
+    utils::{decode_hex, encode_bech32, encode_hex},
+    AssetId, Lovelace, ParseError, PoolId, StakeAddress, TransactionCbor, TxHash, Value,
+};
+
+// Tag of the sets of Conway, optional around arrays
+const SET_TAG: u64 = 258;
+
+// Tag of CBOR encoded in a byte string, around inline datums and reference scripts
+const ENCODED_CBOR_TAG: u64 = 24;
+
+/// Reference to the output of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TxInput {
+    /// Hash of the transaction of the output.
+    pub tx_hash: TxHash,
+    /// Index of the output in the transaction.
+    pub output_index: u64,
+}
+
+/// Formats as `tx_hash#output_index`, like `cardano-cli`.
+impl fmt::Display for TxInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}#{}", self.tx_hash, self.output_index)
+    }
+}
+
+/// A certificate of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Certificate {
+    StakeRegistration(Credential),
+    StakeDeregistration(Credential),
+    StakeDelegation {
+        credential: Credential,
+        pool: PoolId,
+    },
+    /// Registration or update of a stake pool, its other parameters are not decoded.
+    PoolRegistration {
+        pool: PoolId,
+    },
+    PoolRetirement {
+        pool: PoolId,
+        epoch: u64,
+    },
+    /// Stake registration with an explicit deposit, since Conway.
+    Registration {
+        credential: Credential,
+        deposit: Lovelace,
+    },
+    /// Stake deregistration with an explicit refund, since Conway.
+    Deregistration {
+        credential: Credential,
+        refund: Lovelace,
+    },
+    /// Other certificates, e.g. governance ones, by their number in the ledger CDDL.
+    Other(u64),
+}
+
+/// The body of a transaction, what its signers agree to.
+///
+/// Fields of the body that are not listed, e.g. governance votes, are skipped.
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::{transaction::DecodedTransaction, Lovelace};
+///
+/// let transaction = DecodedTransaction::from_cbor_hex("84a3008001800200a0f5f6").unwrap();
+/// assert!(transaction.inputs.is_empty());
+/// assert_eq!(transaction.fee, Lovelace(0));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedTransaction {
+    /// Hash of the transaction, computed from the body.
+    pub hash: TxHash,
+    pub inputs: Vec<TxInput>,
+    pub outputs: Vec<TxOutput>,
+    pub fee: Lovelace,
+    /// First slot the transaction is valid in, also known as validity start.
+    pub invalid_before: Option<u64>,
+    /// First slot the transaction is no longer valid in, also known as TTL.
+    pub invalid_hereafter: Option<u64>,
+    /// Minted quantities, negative when burned.
+    pub mint: Vec<(AssetId, i64)>,
+    pub certificates: Vec<Certificate>,
+    pub withdrawals: Vec<(StakeAddress, Lovelace)>,
+    /// Hex-encoded hashes of the keys that must sign, besides those of the inputs.
+    pub required_signers: Vec<String>,
+    /// Inputs consumed when a script fails.
+    pub collateral: Vec<TxInput>,
+    /// Output created when a script fails.
+    pub collateral_return: Option<TxOutput>,
+    pub total_collateral: Option<Lovelace>,
+    /// Inputs whose outputs are read, but not spent.
+    pub reference_inputs: Vec<TxInput>,
+    /// Hex-encoded hash of the auxiliary data, e.g. metadata.
+    pub auxiliary_data_hash: Option<String>,
+    /// Hex-encoded hash of the redeemers, datums and cost models.
+    pub script_data_hash: Option<String>,
+}
+
+impl DecodedTransaction {
+    /// Decode a whole transaction, as submitted or fetched.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ParseError> {
+        let body = transaction_body(bytes)?;
+        let hash = body_hash(body)?;
+        decode_body(&mut Decoder::new(body), hash).map_err(|cbor_error| error(cbor_error.reason))
+    }
+
+    /// Decode a whole transaction from hex-encoded CBOR.
+    pub fn from_cbor_hex(hex: &str) -> Result<Self, ParseError> {
+        let bytes = decode_hex(hex).ok_or_else(|| error("CBOR is not hex-encoded"))?;
+        Self::from_cbor(&bytes)
+    }
+
+    /// The validity interval, to evaluate native scripts with
+    /// [`NativeScript::evaluate`](crate::script::NativeScript::evaluate).
+    pub fn validity_interval(&self) -> ValidityInterval {
+        ValidityInterval {
+            invalid_before: self.invalid_before,
+            invalid_hereafter: self.invalid_hereafter,
+        }
+    }
+}
+
+impl TransactionCbor {
+    /// Decode [`cbor`](TransactionCbor::cbor).
+    pub fn decoded(&self) -> Result<DecodedTransaction, ParseError> {
+        DecodedTransaction::from_cbor_hex(&self.cbor)
+    }
+}
+
+fn decode_body(decoder: &mut Decoder, hash: TxHash) -> cbor::Result<DecodedTransaction> {
+    let mut transaction = DecodedTransaction {
+        hash,
+        inputs: Vec::new(),
+        outputs: Vec::new(),
+        fee: Lovelace(0),
+        invalid_before: None,
+        invalid_hereafter: None,
+        mint: Vec::new(),
+        certificates: Vec::new(),
+        withdrawals: Vec::new(),
+        required_signers: Vec::new(),
+        collateral: Vec::new(),
+        collateral_return: None,
+        total_collateral: None,
+        reference_inputs: Vec::new(),
+        auxiliary_data_hash: None,
+        script_data_hash: None,
+    };
+
+    let length = decoder.map()?;
+    let mut index = 0;
+    while decoder.has_next(length, index)? {
+        index += 1;
+        match decoder.unsigned()? {
+            0 => transaction.inputs = decode_set(decoder, decode_input)?,
+            1 => transaction.outputs = decode_array(decoder, decode_output)?,
+            2 => transaction.fee = Lovelace(decoder.unsigned()?),
+            3 => transaction.invalid_hereafter = Some(decoder.unsigned()?),
+            4 => transaction.certificates = decode_set(decoder, decode_certificate)?,
+            5 => transaction.withdrawals = decode_withdrawals(decoder)?,
+            7 => transaction.auxiliary_data_hash = Some(decode_hash(decoder, 32)?),
+            8 => transaction.invalid_before = Some(decoder.unsigned()?),
+            9 => transaction.mint = decode_multiasset(decoder, decode_int)?,
+            11 => transaction.script_data_hash = Some(decode_hash(decoder, 32)?),
+            13 => transaction.collateral = decode_set(decoder, decode_input)?,
+            14 => {
+                transaction.required_signers =
+                    decode_set(decoder, |decoder| decode_hash(decoder, 28))?
+            }
+            16 => transaction.collateral_return = Some(decode_output(decoder)?),
+            17 => transaction.total_collateral = Some(Lovelace(decoder.unsigned()?)),
+            18 => transaction.reference_inputs = decode_set(decoder, decode_input)?,
+            _ => decoder.skip()?,
+        }
+    }
+    Ok(transaction)
+}
+
+fn decode_array<'a, T>(
+    decoder: &mut Decoder<'a>,
+    decode_item: fn(&mut Decoder<'a>) -> cbor::Result<T>,
+) -> cbor::Result<Vec<T>> {
+    let length = decoder.array()?;
+    let mut items = Vec::new();
+    while decoder.has_next(length, items.len() as u64)? {
+        items.push(decode_item(decoder)?);
+    }
+    Ok(items)
+}
+
+fn decode_set<'a, T>(
+    decoder: &mut Decoder<'a>,
+    decode_item: fn(&mut Decoder<'a>) -> cbor::Result<T>,
+) -> cbor::Result<Vec<T>> {
+    if decoder.peek_header()? == Header::Tag(SET_TAG) {
+        decoder.tag()?;
+    }
+    decode_array(decoder, decode_item)
+}
+
+// Array of a known length, the number of items of `[kind, ...]` records depends on their kind
+fn expect_array(decoder: &mut Decoder, length: u64) -> cbor::Result<()> {
+    match decoder.array()? {
+        Some(found) if found == length => Ok(()),
+        found => {
+            Err(cbor::error(format!("expected an array of {} items, found {:?}", length, found)))
+        }
+    }
+}
+
+fn decode_hash_bytes(decoder: &mut Decoder, length: usize) -> cbor::Result<Vec<u8>> {
+    let bytes = decoder.bytes()?;
+    if bytes.len() != length {
+        return Err(cbor::error(format!(
+            "expected a {} bytes hash, found {}",
+            length,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+fn decode_hash(decoder: &mut Decoder, length: usize) -> cbor::Result<String> {
+    decode_hash_bytes(decoder, length).map(|bytes| encode_hex(&bytes))
+}
+
+fn decode_int(decoder: &mut Decoder) -> cbor::Result<i64> {
+    let too_large = || cbor::error("integer does not fit in 64 bits");
+    match decoder.header()? {
+        Header::Unsigned(value) => i64::try_from(value).map_err(|_| too_large()),
+        Header::Negative(value) => Ok(-1 - i64::try_from(value).map_err(|_| too_large())?),
+        other => Err(cbor::unexpected("integer", other)),
+    }
+}
+
+fn decode_input(decoder: &mut Decoder) -> cbor::Result<TxInput> {
+    expect_array(decoder, 2)?;
+    let tx_hash = decode_hash(decoder, 32)?.parse()?;
+    Ok(TxInput { tx_hash, output_index: decoder.unsigned()? })
+}
+
+// Outputs are arrays before Babbage, maps since
+fn decode_output(decoder: &mut Decoder) -> cbor::Result<TxOutput> {
+    let mut output = TxOutput {
+        address: Vec::new(),
+        value: Value::new(),
+        datum: OutputDatum::None,
+        script_ref: None,
+    };
+
+    match decoder.peek_header()? {
+        Header::Array(_) => {
+            let length = decoder.array()?;
+            output.address = decoder.bytes()?;
+            output.value = decode_value(decoder)?;
+            if decoder.has_next(length, 2)? {
+                output.datum = OutputDatum::Hash(decoder.bytes()?);
+            }
+            if decoder.has_next(length, 3)? {
+                return Err(cbor::error("legacy output has more than 3 items"));
+            }
+        }
+        _ => {
+            let length = decoder.map()?;
+            let mut index = 0;
+            while decoder.has_next(length, index)? {
+                index += 1;
+                match decoder.unsigned()? {
+                    0 => output.address = decoder.bytes()?,
+                    1 => output.value = decode_value(decoder)?,
+                    2 => {
+                        expect_array(decoder, 2)?;
+                        output.datum = match decoder.unsigned()? {
+                            0 => OutputDatum::Hash(decoder.bytes()?),
+                            1 => OutputDatum::Inline(decode_encoded_cbor(decoder)?),
+                            kind => {
+                                return Err(cbor::error(format!("unknown datum kind {}", kind)))
+                            }
+                        };
+                    }
+                    3 => output.script_ref = Some(decode_encoded_cbor(decoder)?),
+                    _ => decoder.skip()?,
+                }
+            }
+        }
+    }
+    Ok(output)
+}
+
+fn decode_encoded_cbor(decoder: &mut Decoder) -> cbor::Result<Vec<u8>> {
+    match decoder.tag()? {
+        ENCODED_CBOR_TAG => decoder.bytes(),
+        tag => Err(cbor::error(format!("expected encoded CBOR, found tag {}", tag))),
+    }
+}
+
+fn decode_value(decoder: &mut Decoder) -> cbor::Result<Value> {
+    if let Header::Unsigned(_) = decoder.peek_header()? {
+        return Ok(Value::from_lovelace(Lovelace(decoder.unsigned()?)));
+    }
+
+    expect_array(decoder, 2)?;
+    let lovelace = Lovelace(decoder.unsigned()?);
+    decode_multiasset(decoder, |decoder| decoder.unsigned())?.into_iter().try_fold(
+        Value::from_lovelace(lovelace),
+        |value, (asset, quantity)| {
+            value.with_asset(asset, quantity).ok_or_else(|| cbor::error("value overflows"))
+        },
+    )
+}
+
+// Maps of policy IDs to maps of asset names to quantities
+fn decode_multiasset<'a, T>(
+    decoder: &mut Decoder<'a>,
+    decode_quantity: fn(&mut Decoder<'a>) -> cbor::Result<T>,
+) -> cbor::Result<Vec<(AssetId, T)>> {
+    let mut assets = Vec::new();
+    let policies = decoder.map()?;
+    let mut policy_index = 0;
+    while decoder.has_next(policies, policy_index)? {
+        policy_index += 1;
+        let policy_id = decode_hash(decoder, 28)?;
+
+        let names = decoder.map()?;
+        let mut name_index = 0;
+        while decoder.has_next(names, name_index)? {
+            name_index += 1;
+            let asset = AssetId::new(&policy_id, &encode_hex(&decoder.bytes()?))?;
+            assets.push((asset, decode_quantity(decoder)?));
+        }
+    }
+    Ok(assets)
+}
+
+fn decode_withdrawals(decoder: &mut Decoder) -> cbor::Result<Vec<(StakeAddress, Lovelace)>> {
+    let mut withdrawals = Vec::new();
+    let length = decoder.map()?;
+    while decoder.has_next(length, withdrawals.len() as u64)? {
+        let address = DecodedAddress::from_bytes(decoder.bytes()?)
+            .map_err(|error| cbor::error(error.reason))?;
+        let address =
+            address.reward_address().filter(|_| address.payment.is_none()).ok_or_else(|| {
+                cbor::error("withdrawal from an address that is not a reward address")
+            })?;
+        withdrawals.push((address, Lovelace(decoder.unsigned()?)));
+    }
+    Ok(withdrawals)
+}
+
+fn decode_credential(decoder: &mut Decoder) -> cbor::Result<Credential> {
+    expect_array(decoder, 2)?;
+    match decoder.unsigned()? {
+        0 => Ok(Credential::Key(decode_hash(decoder, 28)?)),
+        1 => Ok(Credential::Script(decode_hash(decoder, 28)?)),
+        kind => Err(cbor::error(format!("unknown credential kind {}", kind))),
+    }
+}
+
+fn decode_pool(decoder: &mut Decoder) -> cbor::Result<PoolId> {
+    encode_bech32("pool", &decode_hash_bytes(decoder, 28)?).parse()
+}
+
+// Certificates are `[kind, ...]` arrays, the items after the decoded ones are skipped
+fn decode_certificate(decoder: &mut Decoder) -> cbor::Result<Certificate> {
+    let length = decoder.array()?;
+    let certificate = match decoder.unsigned()? {
+        0 => Certificate::StakeRegistration(decode_credential(decoder)?),
+        1 => Certificate::StakeDeregistration(decode_credential(decoder)?),
+        2 => Certificate::StakeDelegation {
+            credential: decode_credential(decoder)?,
+            pool: decode_pool(decoder)?,
+        },
+        3 => Certificate::PoolRegistration { pool: decode_pool(decoder)? },
+        4 => {
+            Certificate::PoolRetirement { pool: decode_pool(decoder)?, epoch: decoder.unsigned()? }
+        }
+        7 => Certificate::Registration {
+            credential: decode_credential(decoder)?,
+            deposit: Lovelace(decoder.unsigned()?),
+        },
+        8 => Certificate::Deregistration {
+            credential: decode_credential(decoder)?,
+            refund: Lovelace(decoder.unsigned()?),
+        },
+        kind => Certificate::Other(kind),
+    };
+
+    let decoded = match &certificate {
+        Certificate::StakeRegistration(_) | Certificate::StakeDeregistration(_) => 2,
+        Certificate::PoolRegistration { .. } => 2,
+        Certificate::Other(_) => 1,
+        _ => 3,
+    };
+    let mut index = decoded;
+    while decoder.has_next(length, index)? {
+        decoder.skip()?;
+        index += 1;
+    }
+    Ok(certificate)
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("transaction", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Babbage outputs, Conway sets, and most fields of the body
+    const TRANSACTION: &str = "84ac00d9010281825820111111111111111111111111111111111111111111111111111111111111111100018282581d61e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a1a001e8480a400581d61e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a01821a0016e360a1581c22222222222222222222222222222222222222222222222222222222a1436e757405028201d81842182a03d8185182014e4d01000033222220051200120011021a0002bf2003191388048382008200581ce09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a83028200581ce09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a581c555555555555555555555555555555555555555555555555555555558304581c5555555555555555555555555555555555555555555555555555555519012c05a1581de1e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a1903e8075820333333333333333333333333333333333333333333333333333333333333333308190fa009a1581c22222222222222222222222222222222222222222222222222222222a1436e7574220dd90102818258201111111111111111111111111111111111111111111111111111111111111111010e81581ce09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a1281825820444444444444444444444444444444444444444444444444444444444444444400a0f5f6";
+    const KEY_HASH: &str = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+
+    fn input(byte: &str, output_index: u64) -> TxInput {
+        TxInput { tx_hash: byte.repeat(32).parse().unwrap(), output_index }
+    }
+
+    #[test]
+    fn test_decode_transaction() {
+        let transaction = DecodedTransaction::from_cbor_hex(TRANSACTION).unwrap();
+        let nut = AssetId::new(&"22".repeat(28), "6e7574").unwrap();
+        let pool: PoolId = encode_bech32("pool", &[0x55; 28]).parse().unwrap();
+        let key = Credential::Key(KEY_HASH.to_owned());
+
+        assert_eq!(
+            transaction.hash,
+            "3d48d96957053974b4efe4437da52ac39dfd50bf87e634e47e879a5d165b9d6a"
+        );
+        assert_eq!(transaction.inputs, [input("11", 0)]);
+        assert_eq!(transaction.fee, Lovelace(180_000));
+        assert_eq!(
+            transaction.validity_interval(),
+            ValidityInterval { invalid_before: Some(4000), invalid_hereafter: Some(5000) }
+        );
+        assert_eq!(transaction.mint, [(nut.clone(), -3)]);
+        assert_eq!(
+            transaction.certificates,
+            [
+                Certificate::StakeRegistration(key.clone()),
+                Certificate::StakeDelegation { credential: key, pool: pool.clone() },
+                Certificate::PoolRetirement { pool, epoch: 300 },
+            ]
+        );
+        let (stake_address, amount) = &transaction.withdrawals[0];
+        assert!(stake_address.starts_with("stake1u"));
+        assert_eq!(*amount, Lovelace(1000));
+        assert_eq!(transaction.required_signers, [KEY_HASH]);
+        assert_eq!(transaction.collateral, [input("11", 1)]);
+        assert_eq!(transaction.reference_inputs, [input("44", 0)]);
+        assert_eq!(transaction.auxiliary_data_hash, Some("33".repeat(32)));
+        assert_eq!(transaction.script_data_hash, None);
+        assert_eq!(transaction.collateral_return, None);
+
+        let [legacy, babbage] = <[TxOutput; 2]>::try_from(transaction.outputs).unwrap();
+        let address = legacy.decoded_address().unwrap();
+        assert_eq!(address.payment, Some(Credential::Key(KEY_HASH.to_owned())));
+        assert_eq!(legacy.value, Value::from_lovelace(Lovelace(2_000_000)));
+        assert_eq!(legacy.datum, OutputDatum::None);
+
+        assert_eq!(babbage.address, address.bytes);
+        assert_eq!(babbage.value.lovelace(), Lovelace(1_500_000));
+        assert_eq!(babbage.value.quantity_of(&nut), 5);
+        assert_eq!(babbage.datum, OutputDatum::Inline(vec![0x18, 0x2a]));
+        assert_eq!(encode_hex(&babbage.script_ref.unwrap()), "82014e4d01000033222220051200120011");
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        // Not a transaction, then an input hash of 31 bytes
+        assert!(DecodedTransaction::from_cbor_hex("a3008001800200").is_err());
+        let short_hash = format!("84a1008182581f{}00a0f5f6", "11".repeat(31));
+        assert!(DecodedTransaction::from_cbor_hex(&short_hash).is_err());
+    }
+}
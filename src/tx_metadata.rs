@@ -0,0 +1,658 @@
+//! Transaction metadata values, see [`TxMetadatum`], and typed metadata of a label, see
+//! [`BlockFrostApi::metadata_label`].
+
+use futures::stream::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    cbor::{self, unexpected, Decoder, Encoder, Header},
+    utils::{decode_hex, encode_hex},
+    BlockFrostApi, JsonMap, JsonValue, MetadataTxsLabelCbor, MetadataTxsLabelJson, ParseError,
+    TransactionMetadataCbor, TxHash,
+};
+
+// Nesting limit, so malicious data can't overflow the stack
+const MAX_DEPTH: usize = 256;
+
+/// A transaction metadata value, as stored on chain under a metadata label.
+///
+/// Unlike the JSON returned by the API, it keeps byte strings and integers up to 64 bits, and
+/// converts to and from the JSON schemas of `cardano-cli`:
+///
+/// - the "no schema" JSON, where `"0x"`-prefixed strings are bytes,
+///   see [`from_json_no_schema`](TxMetadatum::from_json_no_schema);
+/// - the "detailed schema" JSON, e.g. `{ "int": 42 }`,
+///   see [`from_json_detailed`](TxMetadatum::from_json_detailed).
+///
+/// # Example
+///
+/// ```
+/// use blockfrost::tx_metadata::TxMetadatum;
+/// use serde_json::json;
+///
+/// let metadatum = TxMetadatum::from_json_no_schema(&json!({ "id": "0xcafe", "n": 42 })).unwrap();
+/// assert_eq!(metadatum.get("id").unwrap().as_bytes(), Some(&[0xca, 0xfe][..]));
+///
+/// let cbor = metadatum.to_cbor().unwrap();
+/// assert_eq!(TxMetadatum::from_cbor(&cbor).unwrap(), metadatum);
+/// assert_eq!(metadatum.to_json_detailed()["map"][1]["v"], json!({ "int": 42 }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TxMetadatum {
+    /// Key-value pairs, in their on-chain order.
+    Map(Vec<(TxMetadatum, TxMetadatum)>),
+    List(Vec<TxMetadatum>),
+    /// Integer from `-2^64` to `2^64 - 1`.
+    Int(i128),
+    /// Byte string, up to 64 bytes.
+    Bytes(Vec<u8>),
+    /// Text string, up to 64 bytes of UTF-8.
+    Text(String),
+}
+
+impl TxMetadatum {
+    /// Maximum length in bytes of byte and text strings, longer ones must be split into lists.
+    pub const MAX_STRING_LENGTH: usize = 64;
+    /// Smallest integer.
+    pub const MIN_INT: i128 = -(u64::MAX as i128) - 1;
+    /// Largest integer.
+    pub const MAX_INT: i128 = u64::MAX as i128;
+
+    /// Decode from CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ParseError> {
+        let mut decoder = Decoder::new(bytes);
+        let metadatum = decode(&mut decoder, 0).map_err(|error| self::error(error.reason))?;
+
+        if !decoder.is_finished() {
+            return Err(error("trailing bytes after the metadatum"));
+        }
+        Ok(metadatum)
+    }
+
+    /// Decode from hex-encoded CBOR, optionally prefixed with `\x` as in
+    /// [`TransactionMetadataCbor::cbor_metadata`].
+    pub fn from_cbor_hex(hex: &str) -> Result<Self, ParseError> {
+        let digits = hex.strip_prefix("\\x").unwrap_or(hex);
+        let bytes = decode_hex(digits).ok_or_else(|| error(format!("'{}' is not hex", hex)))?;
+        Self::from_cbor(&bytes)
+    }
+
+    /// Encode to CBOR, failing for strings longer than
+    /// [`MAX_STRING_LENGTH`](TxMetadatum::MAX_STRING_LENGTH) bytes and integers out of range,
+    /// which the ledger rejects.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, ParseError> {
+        let mut encoder = Encoder::new();
+        self.encode(&mut encoder)?;
+        Ok(encoder.into_bytes())
+    }
+
+    /// Text split into a list of chunks of at most
+    /// [`MAX_STRING_LENGTH`](TxMetadatum::MAX_STRING_LENGTH) bytes, at character boundaries.
+    ///
+    /// Metadata standards, e.g. CIP-20 messages, store long strings this way.
+    pub fn text_chunks(text: &str) -> Self {
+        let mut chunks = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let mut end = rest.len().min(Self::MAX_STRING_LENGTH);
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            chunks.push(TxMetadatum::Text(rest[..end].to_owned()));
+            rest = &rest[end..];
+        }
+        TxMetadatum::List(chunks)
+    }
+
+    /// Bytes split into a list of chunks of at most
+    /// [`MAX_STRING_LENGTH`](TxMetadatum::MAX_STRING_LENGTH) bytes.
+    pub fn bytes_chunks(bytes: &[u8]) -> Self {
+        let chunks = bytes.chunks(Self::MAX_STRING_LENGTH);
+        TxMetadatum::List(chunks.map(|chunk| TxMetadatum::Bytes(chunk.to_vec())).collect())
+    }
+
+    /// Convert from the "no schema" JSON of `cardano-cli`.
+    ///
+    /// Numbers must be integers, strings are bytes if they are `"0x"`-prefixed lowercase hex,
+    /// integers if they are decimal integers that don't fit in 64 bits (see
+    /// [`to_json_no_schema`](TxMetadatum::to_json_no_schema)), and text otherwise. Arrays are lists
+    /// and objects are maps. Object keys are converted like strings, except decimal integers which
+    /// are integers. `null` and booleans are rejected.
+    pub fn from_json_no_schema(json: &JsonValue) -> Result<Self, ParseError> {
+        match json {
+            JsonValue::Number(number) => json_integer(number).map(TxMetadatum::Int),
+            JsonValue::String(text) => match string_integer(text) {
+                Some(integer)
+                    if i64::try_from(integer).is_err() && u64::try_from(integer).is_err() =>
+                {
+                    Ok(TxMetadatum::Int(integer))
+                }
+                _ => Ok(no_schema_string(text)),
+            },
+            JsonValue::Array(items) => items
+                .iter()
+                .map(Self::from_json_no_schema)
+                .collect::<Result<_, _>>()
+                .map(Self::List),
+            JsonValue::Object(entries) => entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key.parse::<i128>() {
+                        Ok(integer) => TxMetadatum::Int(integer),
+                        Err(_) => no_schema_string(key),
+                    };
+                    Ok((key, Self::from_json_no_schema(value)?))
+                })
+                .collect::<Result<_, _>>()
+                .map(Self::Map),
+            other => Err(error(format!("{} has no metadata equivalent", other))),
+        }
+    }
+
+    /// Convert to the "no schema" JSON of `cardano-cli`.
+    ///
+    /// Bytes become `"0x"`-prefixed hex strings, and map keys become strings: integers in
+    /// decimal, lists and maps as their JSON text. Integers that don't fit in 64 bits become
+    /// decimal strings, and duplicate keys keep the last value.
+    pub fn to_json_no_schema(&self) -> JsonValue {
+        match self {
+            TxMetadatum::Map(entries) => {
+                let object = entries.iter().map(|(key, value)| {
+                    let key = match key.to_json_no_schema() {
+                        JsonValue::String(text) => text,
+                        other => other.to_string(),
+                    };
+                    (key, value.to_json_no_schema())
+                });
+                JsonValue::Object(object.collect())
+            }
+            TxMetadatum::List(items) => items.iter().map(Self::to_json_no_schema).collect(),
+            TxMetadatum::Int(integer) => json_from_integer(*integer),
+            TxMetadatum::Bytes(bytes) => JsonValue::String(format!("0x{}", encode_hex(bytes))),
+            TxMetadatum::Text(text) => JsonValue::String(text.clone()),
+        }
+    }
+
+    /// Convert from the "detailed schema" JSON of `cardano-cli`: objects with a single `"int"`,
+    /// `"bytes"` (hex), `"string"`, `"list"` or `"map"` (array of `{ "k": .., "v": .. }`) field.
+    ///
+    /// Integers can also be decimal strings, for those that don't fit in 64 bits.
+    pub fn from_json_detailed(json: &JsonValue) -> Result<Self, ParseError> {
+        let invalid = || error(format!("{} is not a detailed schema value", json));
+
+        let (kind, value) = match json.as_object() {
+            Some(object) if object.len() == 1 => object.iter().next().ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+        match (kind.as_str(), value) {
+            ("int", JsonValue::Number(number)) => json_integer(number).map(TxMetadatum::Int),
+            ("int", JsonValue::String(text)) => string_integer(text)
+                .map(TxMetadatum::Int)
+                .ok_or_else(|| error(format!("'{}' is not a metadata integer", text))),
+            ("bytes", JsonValue::String(hex)) => decode_hex(hex)
+                .map(TxMetadatum::Bytes)
+                .ok_or_else(|| error(format!("'{}' is not hex", hex))),
+            ("string", JsonValue::String(text)) => Ok(TxMetadatum::Text(text.clone())),
+            ("list", JsonValue::Array(items)) => {
+                items.iter().map(Self::from_json_detailed).collect::<Result<_, _>>().map(Self::List)
+            }
+            ("map", JsonValue::Array(entries)) => entries
+                .iter()
+                .map(|entry| match (entry.get("k"), entry.get("v")) {
+                    (Some(key), Some(value)) => {
+                        Ok((Self::from_json_detailed(key)?, Self::from_json_detailed(value)?))
+                    }
+                    _ => Err(error(format!("{} is not a map entry with 'k' and 'v'", entry))),
+                })
+                .collect::<Result<_, _>>()
+                .map(Self::Map),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Convert to the "detailed schema" JSON of `cardano-cli`.
+    ///
+    /// Integers that don't fit in 64 bits become decimal strings.
+    pub fn to_json_detailed(&self) -> JsonValue {
+        let (kind, value) = match self {
+            TxMetadatum::Map(entries) => {
+                let entries = entries.iter().map(|(key, value)| {
+                    let mut entry = JsonMap::new();
+                    entry.insert("k".to_owned(), key.to_json_detailed());
+                    entry.insert("v".to_owned(), value.to_json_detailed());
+                    JsonValue::Object(entry)
+                });
+                ("map", entries.collect())
+            }
+            TxMetadatum::List(items) => {
+                ("list", items.iter().map(Self::to_json_detailed).collect())
+            }
+            TxMetadatum::Int(integer) => ("int", json_from_integer(*integer)),
+            TxMetadatum::Bytes(bytes) => ("bytes", JsonValue::String(encode_hex(bytes))),
+            TxMetadatum::Text(text) => ("string", JsonValue::String(text.clone())),
+        };
+        let mut object = JsonMap::new();
+        object.insert(kind.to_owned(), value);
+        JsonValue::Object(object)
+    }
+
+    /// Value of the first entry with a text key, if this is a map.
+    pub fn get(&self, key: &str) -> Option<&TxMetadatum> {
+        self.as_map()?.iter().find_map(|(entry_key, value)| match entry_key {
+            TxMetadatum::Text(text) if text == key => Some(value),
+            _ => None,
+        })
+    }
+
+    pub fn as_map(&self) -> Option<&[(TxMetadatum, TxMetadatum)]> {
+        match self {
+            TxMetadatum::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[TxMetadatum]> {
+        match self {
+            TxMetadatum::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            TxMetadatum::Int(integer) => Some(*integer),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            TxMetadatum::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            TxMetadatum::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn encode(&self, encoder: &mut Encoder) -> Result<(), ParseError> {
+        match self {
+            TxMetadatum::Map(entries) => {
+                encoder.map(entries.len());
+                for (key, value) in entries {
+                    key.encode(encoder)?;
+                    value.encode(encoder)?;
+                }
+            }
+            TxMetadatum::List(items) => {
+                encoder.array(items.len());
+                for item in items {
+                    item.encode(encoder)?;
+                }
+            }
+            TxMetadatum::Int(integer) => match u64::try_from(*integer) {
+                Ok(unsigned) => {
+                    encoder.unsigned(unsigned);
+                }
+                Err(_) => {
+                    let negative = u64::try_from(-1 - *integer)
+                        .map_err(|_| error(format!("integer {} is out of range", integer)))?;
+                    encoder.negative(negative);
+                }
+            },
+            TxMetadatum::Bytes(bytes) => {
+                check_length(bytes.len())?;
+                encoder.bytes(bytes);
+            }
+            TxMetadatum::Text(text) => {
+                check_length(text.len())?;
+                encoder.text(text);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn decode(decoder: &mut Decoder, depth: usize) -> cbor::Result<TxMetadatum> {
+    if depth > MAX_DEPTH {
+        return Err(cbor::error("metadatum is nested too deeply"));
+    }
+
+    match decoder.peek_header()? {
+        Header::Unsigned(_) | Header::Negative(_) => match decoder.header()? {
+            Header::Unsigned(value) => Ok(TxMetadatum::Int(value.into())),
+            Header::Negative(value) => Ok(TxMetadatum::Int(-1 - i128::from(value))),
+            _ => unreachable!("header was peeked"),
+        },
+        Header::Bytes(_) => decoder.bytes().map(TxMetadatum::Bytes),
+        Header::Text(_) => decoder.text().map(TxMetadatum::Text),
+        Header::Array(_) => {
+            let length = decoder.array()?;
+            let mut items = Vec::new();
+            while decoder.has_next(length, items.len() as u64)? {
+                items.push(decode(decoder, depth + 1)?);
+            }
+            Ok(TxMetadatum::List(items))
+        }
+        Header::Map(_) => {
+            let length = decoder.map()?;
+            let mut entries = Vec::new();
+            while decoder.has_next(length, entries.len() as u64)? {
+                let key = decode(decoder, depth + 1)?;
+                let value = decode(decoder, depth + 1)?;
+                entries.push((key, value));
+            }
+            Ok(TxMetadatum::Map(entries))
+        }
+        other => Err(unexpected("metadatum", other)),
+    }
+}
+
+fn no_schema_string(text: &str) -> TxMetadatum {
+    let is_lowercase_hex = |hex: &str| !hex.chars().any(|c| c.is_ascii_uppercase());
+
+    match text.strip_prefix("0x").filter(|hex| is_lowercase_hex(hex)).and_then(decode_hex) {
+        Some(bytes) => TxMetadatum::Bytes(bytes),
+        None => TxMetadatum::Text(text.to_owned()),
+    }
+}
+
+fn json_integer(number: &serde_json::Number) -> Result<i128, ParseError> {
+    match (number.as_i64(), number.as_u64()) {
+        (Some(integer), _) => Ok(integer.into()),
+        (_, Some(integer)) => Ok(integer.into()),
+        _ => Err(error(format!("{} is not an integer", number))),
+    }
+}
+
+// Decimal integer within the metadata range, `json_from_integer` writes those beyond 64 bits so
+fn string_integer(text: &str) -> Option<i128> {
+    let range = TxMetadatum::MIN_INT..=TxMetadatum::MAX_INT;
+    text.parse().ok().filter(|integer| range.contains(integer))
+}
+
+fn json_from_integer(integer: i128) -> JsonValue {
+    if let Ok(integer) = i64::try_from(integer) {
+        integer.into()
+    } else if let Ok(integer) = u64::try_from(integer) {
+        integer.into()
+    } else {
+        integer.to_string().into()
+    }
+}
+
+fn check_length(length: usize) -> Result<(), ParseError> {
+    if length > TxMetadatum::MAX_STRING_LENGTH {
+        return Err(error(format!(
+            "string of {} bytes is longer than {} bytes, split it into a list",
+            length,
+            TxMetadatum::MAX_STRING_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+fn error(reason: impl ToString) -> ParseError {
+    ParseError::new("metadata", reason)
+}
+
+/// A transaction with the JSON metadata of a label deserialized into `T`, created by
+/// [`BlockFrostApi::metadata_label`].
+///
+/// Transactions without metadata for the label deserialize from `null`, use an `Option` to accept
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataEntry<T> {
+    /// Transaction hash that contains the metadata.
+    pub tx_hash: TxHash,
+    /// The deserialized metadata, or why it doesn't match `T`.
+    pub metadata: Result<T, ParseError>,
+}
+
+impl<T: DeserializeOwned> From<MetadataTxsLabelJson> for MetadataEntry<T> {
+    fn from(entry: MetadataTxsLabelJson) -> Self {
+        let json = entry.json_metadata.unwrap_or_default();
+        let metadata = serde_json::from_value(json).map_err(error);
+        Self { tx_hash: entry.tx_hash, metadata }
+    }
+}
+
+impl BlockFrostApi {
+    /// Like [`metadata_txs_by_label`](BlockFrostApi::metadata_txs_by_label), with the metadata of
+    /// each transaction deserialized into `T`.
+    ///
+    /// Metadata that doesn't match `T` fails its own [`MetadataEntry`], not the whole page.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(api: blockfrost::BlockFrostApi) -> blockfrost::Result<()> {
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Price {
+    ///     ticker: String,
+    ///     price: u64,
+    /// }
+    ///
+    /// for entry in api.metadata_label::<Price>("1968").await? {
+    ///     match entry.metadata {
+    ///         Ok(price) => println!("{}: {} {}", entry.tx_hash, price.ticker, price.price),
+    ///         Err(error) => println!("{}: {}", entry.tx_hash, error),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn metadata_label<T: DeserializeOwned>(
+        &self,
+        label: &str,
+    ) -> crate::Result<Vec<MetadataEntry<T>>> {
+        let entries = self.metadata_txs_by_label(label).await?;
+        Ok(entries.into_iter().map(MetadataEntry::from).collect())
+    }
+
+    /// Like [`metadata_label`](BlockFrostApi::metadata_label), for every page, see
+    /// [`metadata_txs_by_label_all`](BlockFrostApi::metadata_txs_by_label_all).
+    pub fn metadata_label_all<T: DeserializeOwned>(
+        &self,
+        label: &str,
+    ) -> impl Stream<Item = crate::Result<Vec<MetadataEntry<T>>>> + '_ {
+        self.metadata_txs_by_label_all(label)
+            .map(|page| page.map(|entries| entries.into_iter().map(MetadataEntry::from).collect()))
+    }
+}
+
+impl TransactionMetadataCbor {
+    /// Decode [`cbor_metadata`](TransactionMetadataCbor::cbor_metadata), `None` if it's absent.
+    ///
+    /// The API stores it as a map from the label to the value, this returns the value.
+    pub fn metadatum(&self) -> Option<Result<TxMetadatum, ParseError>> {
+        let hex = self.cbor_metadata.as_deref()?;
+        Some(label_value(hex, &self.label))
+    }
+}
+
+impl MetadataTxsLabelCbor {
+    /// Decode [`cbor_metadata`](MetadataTxsLabelCbor::cbor_metadata), `None` if it's absent.
+    ///
+    /// The API stores it as a map from the requested `label` to the value, this returns the
+    /// value.
+    pub fn metadatum(&self, label: &str) -> Option<Result<TxMetadatum, ParseError>> {
+        let hex = self.cbor_metadata.as_deref()?;
+        Some(label_value(hex, label))
+    }
+}
+
+// Decode metadata, unwrapping the value from a map with the label as its only key
+fn label_value(hex: &str, label: &str) -> Result<TxMetadatum, ParseError> {
+    let is_label = |key: &TxMetadatum| key.as_int().is_some_and(|key| label.parse() == Ok(key));
+    match TxMetadatum::from_cbor_hex(hex)? {
+        TxMetadatum::Map(mut entries) if entries.len() == 1 && is_label(&entries[0].0) => {
+            Ok(entries.remove(0).1)
+        }
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn text(text: &str) -> TxMetadatum {
+        TxMetadatum::Text(text.to_owned())
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        // { 0: { "Combination": [1, 1, 1, 1, 1, 12] } }, as returned by the API
+        let hex = "a100a16b436f6d62696e6174696f6e8601010101010c";
+        let metadatum = TxMetadatum::from_cbor_hex(&format!("\\x{}", hex)).unwrap();
+
+        let numbers = [1, 1, 1, 1, 1, 12].into_iter().map(TxMetadatum::Int).collect();
+        let expected = TxMetadatum::Map(vec![(
+            TxMetadatum::Int(0),
+            TxMetadatum::Map(vec![(text("Combination"), TxMetadatum::List(numbers))]),
+        )]);
+        assert_eq!(metadatum, expected);
+        assert_eq!(encode_hex(&metadatum.to_cbor().unwrap()), hex);
+
+        let response = TransactionMetadataCbor {
+            label: "0".to_owned(),
+            cbor_metadata: Some(format!("\\x{}", hex)),
+        };
+        assert_eq!(response.metadatum().unwrap().unwrap(), expected.as_map().unwrap()[0].1);
+
+        // A single entry map under another label is the value itself
+        let other_label = TransactionMetadataCbor { label: "1".to_owned(), ..response };
+        assert_eq!(other_label.metadatum().unwrap().unwrap(), expected);
+        let tx_hash = "36fdff68dfe3660f1ceea60f018a0fd7a83da13def229108794c397a879b0436";
+        let response = MetadataTxsLabelCbor {
+            tx_hash: tx_hash.parse().unwrap(),
+            cbor_metadata: Some(hex.into()),
+        };
+        assert_eq!(response.metadatum("0").unwrap().unwrap(), expected.as_map().unwrap()[0].1);
+        assert_eq!(response.metadatum("1").unwrap().unwrap(), expected);
+
+        // Indefinite-length text chunks and list, negative integer
+        let chunked = TxMetadatum::from_cbor_hex("9f7f6261626163ff3863ff").unwrap();
+        assert_eq!(chunked, TxMetadatum::List(vec![text("abc"), TxMetadatum::Int(-100)]));
+
+        let extremes = TxMetadatum::List(vec![
+            TxMetadatum::Int(TxMetadatum::MIN_INT),
+            TxMetadatum::Int(TxMetadatum::MAX_INT),
+        ]);
+        let cbor = extremes.to_cbor().unwrap();
+        assert_eq!(TxMetadatum::from_cbor(&cbor).unwrap(), extremes);
+        assert!(TxMetadatum::Int(TxMetadatum::MAX_INT + 1).to_cbor().is_err());
+    }
+
+    #[test]
+    fn test_string_length() {
+        let long = "é".repeat(40);
+        assert!(TxMetadatum::Text(long.clone()).to_cbor().is_err());
+        assert!(TxMetadatum::Bytes(vec![0; 65]).to_cbor().is_err());
+
+        let chunks = TxMetadatum::text_chunks(&long);
+        let chunks = chunks.as_list().unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_text().unwrap().len(), 64);
+        assert_eq!(chunks.iter().map(|chunk| chunk.as_text().unwrap()).collect::<String>(), long);
+
+        let chunks = TxMetadatum::bytes_chunks(&[0; 65]);
+        assert!(chunks.to_cbor().is_ok());
+        assert_eq!(chunks.as_list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_json_no_schema() {
+        let json = json!({ "7": ["text", "0xcafe", "0xCAFE", -2], "0x01": { "nested": 1 } });
+        let metadatum = TxMetadatum::from_json_no_schema(&json).unwrap();
+
+        let entries = metadatum.as_map().unwrap();
+        assert_eq!(entries[0].0, TxMetadatum::Bytes(vec![1]));
+        assert_eq!(entries[1].0, TxMetadatum::Int(7));
+        let list = entries[1].1.as_list().unwrap();
+        assert_eq!(list[1], TxMetadatum::Bytes(vec![0xca, 0xfe]));
+        assert_eq!(list[2], text("0xCAFE"));
+
+        assert_eq!(metadatum.to_json_no_schema(), json);
+
+        // Integers beyond 64 bits are decimal strings, smaller ones in strings stay text
+        let json = json!(["-18446744073709551616", "-1", "18446744073709551616"]);
+        let list = TxMetadatum::from_json_no_schema(&json).unwrap();
+        let expected =
+            [TxMetadatum::Int(TxMetadatum::MIN_INT), text("-1"), text("18446744073709551616")];
+        assert_eq!(list.as_list().unwrap(), expected);
+        assert_eq!(list.to_json_no_schema(), json);
+
+        assert!(TxMetadatum::from_json_no_schema(&json!([true])).is_err());
+        assert!(TxMetadatum::from_json_no_schema(&json!(1.5)).is_err());
+    }
+
+    #[test]
+    fn test_metadata_entries() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Price {
+            ticker: String,
+            price: u64,
+        }
+
+        let entries: Vec<MetadataTxsLabelJson> = serde_json::from_value(json!([
+            {
+                "tx_hash": "257d75c8ddb0434e9b63e29ebb6241add2b835a307aa33aedba2effe09ed4ec8",
+                "json_metadata": { "ticker": "ADA", "price": 42 }
+            },
+            {
+                "tx_hash": "e865f2cc01ca7381cf98dcdc4de07a5e8674b8ea16e6a18e3ed60c186fde2b9c",
+                "json_metadata": { "ticker": "ADA", "price": "unknown" }
+            },
+            {
+                "tx_hash": "4237501da3cfdd53ade91e8911e764bd0699d88fd43b12f44a1f459b89bc91be",
+                "json_metadata": null
+            }
+        ]))
+        .unwrap();
+
+        let prices: Vec<MetadataEntry<Price>> =
+            entries.iter().cloned().map(MetadataEntry::from).collect();
+        assert_eq!(prices[0].metadata, Ok(Price { ticker: "ADA".to_owned(), price: 42 }));
+        assert!(prices[1].metadata.is_err());
+        assert!(prices[2].metadata.is_err());
+
+        let optional: Vec<MetadataEntry<Option<Price>>> =
+            entries.into_iter().map(MetadataEntry::from).collect();
+        assert_eq!(optional[2].metadata, Ok(None));
+    }
+
+    #[test]
+    fn test_json_detailed() {
+        let json = json!({ "map": [
+            { "k": { "string": "id" }, "v": { "bytes": "cafe" } },
+            { "k": { "int": 1 }, "v": { "list": [{ "int": -1 }] } },
+        ]});
+        let metadatum = TxMetadatum::from_json_detailed(&json).unwrap();
+
+        assert_eq!(metadatum.get("id"), Some(&TxMetadatum::Bytes(vec![0xca, 0xfe])));
+        assert_eq!(metadatum.to_json_detailed(), json);
+        assert!(TxMetadatum::from_json_detailed(&json!({ "int": 1, "bytes": "" })).is_err());
+        let min = TxMetadatum::Int(TxMetadatum::MIN_INT);
+        let min_json = json!({ "int": "-18446744073709551616" });
+        assert_eq!(min.to_json_detailed(), min_json);
+        assert_eq!(TxMetadatum::from_json_detailed(&min_json).unwrap(), min);
+        assert_eq!(
+            TxMetadatum::from_json_detailed(&json!({ "int": "1" })).unwrap().as_int(),
+            Some(1)
+        );
+        assert!(
+            TxMetadatum::from_json_detailed(&json!({ "int": "-18446744073709551617" })).is_err()
+        );
+        assert!(TxMetadatum::from_json_detailed(&json!({ "int": "one" })).is_err());
+    }
+}
@@ -1,3 +1,8 @@
+use bech32::{FromBase32, ToBase32, Variant};
+use blake2::{
+    digest::consts::{U20, U28, U32},
+    Blake2b, Digest,
+};
 use reqwest::{
     header::{HeaderMap, HeaderValue},
     Client,
@@ -32,3 +37,80 @@ pub(crate) fn build_header_map(project_id: &str) -> HeaderMap {
     header_map.insert("User-Agent", user_agent);
     header_map
 }
+
+pub(crate) fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => Some((hex_digit(*high)? << 4) | hex_digit(*low)?),
+            _ => None,
+        })
+        .collect()
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|digit| digit as u8)
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub(crate) fn is_hex(text: &str) -> bool {
+    text.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+// Decodes a bech32 string into its human-readable part and data bytes.
+pub(crate) fn decode_bech32(text: &str) -> Result<(String, Vec<u8>), String> {
+    let (hrp, data, variant) = bech32::decode(text).map_err(|error| error.to_string())?;
+    if variant != Variant::Bech32 {
+        return Err("uses the bech32m checksum".to_owned());
+    }
+    let bytes = Vec::<u8>::from_base32(&data).map_err(|error| error.to_string())?;
+    Ok((hrp, bytes))
+}
+
+// Safety: the human-readable parts used in this crate are valid, so this never fails.
+pub(crate) fn encode_bech32(hrp: &str, bytes: &[u8]) -> String {
+    bech32::encode(hrp, bytes.to_base32(), Variant::Bech32).unwrap()
+}
+
+// CRC-32 (IEEE 802.3), used in Byron addresses.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+// Blake2b with a 160 bits digest, used in CIP-14 asset fingerprints.
+pub(crate) fn blake2b_160(bytes: &[u8]) -> [u8; 20] {
+    Blake2b::<U20>::digest(bytes).into()
+}
+
+// Blake2b with a 256 bits digest, used in transaction and datum hashes.
+pub(crate) fn blake2b_256(bytes: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(bytes).into()
+}
+
+// Blake2b with a 224 bits digest, used in script and key hashes.
+pub(crate) fn blake2b_224(bytes: &[u8]) -> [u8; 28] {
+    Blake2b::<U28>::digest(bytes).into()
+}
+
+// CRC-8 (polynomial 0x07), used in CIP-67 asset name labels.
+pub(crate) fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
@@ -0,0 +1,690 @@
+//! Typed amounts: [`Lovelace`], [`AssetId`] and the multi-asset [`Value`].
+//!
+//! The API returns every amount as a `String`, the types in this module parse those strings and
+//! are returned by accessor methods of the response types, e.g. [`Transaction::fees_lovelace`] and
+//! [`AddressUtxo::value`].
+//!
+//! [`AssetId`] also computes CIP-14 fingerprints and decodes CIP-67 labelled asset names.
+
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use crate::{
+    utils::{blake2b_160, crc8, decode_hex, encode_bech32, is_hex},
+    Account, Address, AddressTotal, AddressUtxo, Amount, Asset, AssetDetails, AssetFingerprint,
+    ParseError, Pool, Transaction, TransactionUtxosInput, TransactionUtxosOutput,
+};
+
+/// The unit used by the API for ADA amounts.
+pub const LOVELACE_UNIT: &str = "lovelace";
+
+/// Amount of lovelaces in one ADA.
+pub const LOVELACES_PER_ADA: u64 = 1_000_000;
+
+/// An amount of ADA, in lovelaces.
+///
+/// Displayed in ADA with all 6 decimal places, e.g. `1.500000 ADA`.
+///
+/// # Example
+///
+/// ```
+/// # use blockfrost::Lovelace;
+/// let fee: Lovelace = "170000".parse().unwrap();
+///
+/// assert_eq!(fee, Lovelace(170_000));
+/// assert_eq!(fee.to_string(), "0.170000 ADA");
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lovelace(pub u64);
+
+impl Lovelace {
+    /// Create from an amount of whole ADA, `None` on overflow.
+    pub fn from_ada(ada: u64) -> Option<Self> {
+        ada.checked_mul(LOVELACES_PER_ADA).map(Self)
+    }
+
+    /// Checked addition, `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Checked subtraction, `None` if `other` is bigger than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+}
+
+impl From<u64> for Lovelace {
+    fn from(lovelaces: u64) -> Self {
+        Self(lovelaces)
+    }
+}
+
+impl From<Lovelace> for u64 {
+    fn from(Lovelace(lovelaces): Lovelace) -> Self {
+        lovelaces
+    }
+}
+
+impl FromStr for Lovelace {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_quantity(text).map(Self)
+    }
+}
+
+impl fmt::Display for Lovelace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ada = self.0 / LOVELACES_PER_ADA;
+        let decimals = self.0 % LOVELACES_PER_ADA;
+        write!(f, "{}.{:06} ADA", ada, decimals)
+    }
+}
+
+/// Identifier of a native asset: the policy ID and the asset name.
+///
+/// The API identifies assets by their unit, the concatenation of the hex-encoded policy ID and
+/// asset name, the [`FromStr`] and [`Display`](fmt::Display) implementations use that format.
+///
+/// # Example
+///
+/// ```
+/// # use blockfrost::AssetId;
+/// let unit = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a76e7574636f696e";
+/// let asset: AssetId = unit.parse().unwrap();
+///
+/// assert_eq!(asset.policy_id, "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7");
+/// assert_eq!(asset.asset_name, "6e7574636f696e");
+/// assert_eq!(asset.to_string(), unit);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssetId {
+    /// Hex-encoded policy ID, 28 bytes.
+    pub policy_id: String,
+    /// Hex-encoded asset name, up to 32 bytes, can be empty.
+    pub asset_name: String,
+}
+
+impl AssetId {
+    /// Length of a hex-encoded policy ID.
+    pub const POLICY_ID_HEX_LENGTH: usize = 56;
+    /// Maximum length of a hex-encoded asset name.
+    pub const MAX_ASSET_NAME_HEX_LENGTH: usize = 64;
+
+    /// Create from the hex-encoded policy ID and asset name, validating both.
+    pub fn new(policy_id: &str, asset_name: &str) -> Result<Self, ParseError> {
+        let error = |reason| Err(ParseError::new("asset id", reason));
+
+        if policy_id.len() != Self::POLICY_ID_HEX_LENGTH || !is_hex(policy_id) {
+            return error(format!("policy id '{}' is not 28 hex-encoded bytes", policy_id));
+        }
+        if asset_name.len() > Self::MAX_ASSET_NAME_HEX_LENGTH || decode_hex(asset_name).is_none() {
+            return error(format!("asset name '{}' is not up to 32 hex-encoded bytes", asset_name));
+        }
+        Ok(Self { policy_id: policy_id.to_lowercase(), asset_name: asset_name.to_lowercase() })
+    }
+
+    /// Raw bytes of the asset name.
+    pub fn asset_name_bytes(&self) -> Vec<u8> {
+        // Safety: validated when created
+        decode_hex(&self.asset_name).unwrap_or_default()
+    }
+
+    /// The CIP-14 fingerprint, the bech32 `asset` encoding of the Blake2b-160 hash of the policy ID
+    /// and asset name bytes.
+    pub fn fingerprint(&self) -> AssetFingerprint {
+        // Safety: validated when created
+        let mut bytes = decode_hex(&self.policy_id).unwrap_or_default();
+        bytes.extend(self.asset_name_bytes());
+
+        let fingerprint = encode_bech32("asset", &blake2b_160(&bytes));
+        AssetFingerprint::try_from(fingerprint).expect("fingerprints are 20 bytes")
+    }
+
+    /// The CIP-67 label prefixed to the asset name, if any.
+    pub fn label(&self) -> Option<AssetLabel> {
+        let prefix: [u8; 4] = self.asset_name_bytes().get(..4)?.try_into().ok()?;
+        AssetLabel::from_prefix(prefix)
+    }
+
+    /// The same asset name with another CIP-67 label, replacing the current one if any.
+    ///
+    /// For example, the CIP-68 reference NFT of a user token is `with_label(AssetLabel::ReferenceNft)`.
+    /// Fails if the unlabelled name is longer than 28 bytes, leaving no room for the label.
+    pub fn with_label(&self, label: AssetLabel) -> Result<AssetId, ParseError> {
+        let name = if self.label().is_some() { &self.asset_name[8..] } else { &self.asset_name };
+        AssetId::new(&self.policy_id, &format!("{}{}", label.prefix_hex(), name))
+    }
+
+    /// The asset name as UTF-8 text, without its CIP-67 label.
+    ///
+    /// `None` if the name is empty, not UTF-8 or contains control characters, for example when it
+    /// is a hash.
+    pub fn asset_name_utf8(&self) -> Option<String> {
+        let mut bytes = self.asset_name_bytes();
+        if self.label().is_some() {
+            bytes.drain(..4);
+        }
+
+        let name = String::from_utf8(bytes).ok()?;
+        (!name.is_empty() && !name.chars().any(char::is_control)).then_some(name)
+    }
+}
+
+impl FromStr for AssetId {
+    type Err = ParseError;
+
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        if unit.len() < Self::POLICY_ID_HEX_LENGTH
+            || !unit.is_char_boundary(Self::POLICY_ID_HEX_LENGTH)
+        {
+            let reason = format!("unit '{}' is shorter than a policy id", unit);
+            return Err(ParseError::new("asset id", reason));
+        }
+        let (policy_id, asset_name) = unit.split_at(Self::POLICY_ID_HEX_LENGTH);
+        Self::new(policy_id, asset_name)
+    }
+}
+
+impl fmt::Display for AssetId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.policy_id, self.asset_name)
+    }
+}
+
+/// CIP-67 asset name label, telling the purpose of a token.
+///
+/// Labels are encoded in the first 4 bytes of the asset name, with a CRC-8 checksum.
+///
+/// # Example
+///
+/// ```
+/// # use blockfrost::{AssetId, AssetLabel};
+/// let unit = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7000de1404e7574";
+/// let asset: AssetId = unit.parse().unwrap();
+///
+/// assert_eq!(asset.label(), Some(AssetLabel::Nft));
+/// assert_eq!(asset.asset_name_utf8().unwrap(), "Nut");
+/// assert_eq!(AssetLabel::Nft.prefix_hex(), "000de140");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssetLabel {
+    /// Label 100, CIP-68 reference NFT holding the datum of a user token.
+    ReferenceNft,
+    /// Label 222, CIP-68 NFT.
+    Nft,
+    /// Label 333, CIP-68 fungible token.
+    Ft,
+    /// Label 444, CIP-68 rich fungible token.
+    Rft,
+    /// Any other valid label.
+    Other(u16),
+}
+
+impl AssetLabel {
+    /// Label from its number.
+    pub fn from_number(number: u16) -> Self {
+        match number {
+            100 => Self::ReferenceNft,
+            222 => Self::Nft,
+            333 => Self::Ft,
+            444 => Self::Rft,
+            number => Self::Other(number),
+        }
+    }
+
+    /// Number of the label.
+    pub fn number(self) -> u16 {
+        match self {
+            Self::ReferenceNft => 100,
+            Self::Nft => 222,
+            Self::Ft => 333,
+            Self::Rft => 444,
+            Self::Other(number) => number,
+        }
+    }
+
+    // Format: [0000 | 16 bits label | 8 bits CRC-8 of the label | 0000]
+    fn from_prefix(prefix: [u8; 4]) -> Option<Self> {
+        let bits = u32::from_be_bytes(prefix);
+        if bits & 0xf000_000f != 0 {
+            return None;
+        }
+        let number = (bits >> 12) as u16;
+        let checksum = (bits >> 4) as u8;
+
+        (crc8(&number.to_be_bytes()) == checksum).then(|| Self::from_number(number))
+    }
+
+    /// Hex-encoded 4 bytes prefix of the asset name.
+    pub fn prefix_hex(self) -> String {
+        let number = self.number();
+        let checksum = crc8(&number.to_be_bytes());
+        format!("{:08x}", (number as u32) << 12 | (checksum as u32) << 4)
+    }
+}
+
+/// Unit of an [`Amount`], either lovelaces or a native asset.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssetUnit {
+    Lovelace,
+    Asset(AssetId),
+}
+
+impl FromStr for AssetUnit {
+    type Err = ParseError;
+
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        if unit == LOVELACE_UNIT {
+            Ok(Self::Lovelace)
+        } else {
+            unit.parse().map(Self::Asset)
+        }
+    }
+}
+
+impl fmt::Display for AssetUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetUnit::Lovelace => write!(f, "{}", LOVELACE_UNIT),
+            AssetUnit::Asset(asset) => write!(f, "{}", asset),
+        }
+    }
+}
+
+/// Multi-asset value: an amount of lovelaces plus native asset quantities.
+///
+/// Assets with a zero quantity are never stored.
+///
+/// # Example
+///
+/// ```
+/// # use blockfrost::{Amount, Lovelace, Value};
+/// let amounts = vec![
+///     Amount { unit: "lovelace".into(), quantity: "42000000".into() },
+///     Amount {
+///         unit: "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a76e7574636f696e".into(),
+///         quantity: "12".into(),
+///     },
+/// ];
+/// let value = Value::from_amounts(&amounts).unwrap();
+/// let fee = Value::from_lovelace(Lovelace(170_000));
+///
+/// let change = value.checked_sub(&fee).unwrap();
+/// assert_eq!(change.lovelace(), Lovelace(41_830_000));
+/// assert_eq!(change.assets().count(), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Value {
+    lovelace: Lovelace,
+    assets: BTreeMap<AssetId, u64>,
+}
+
+impl Value {
+    /// Create an empty value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a value containing only lovelaces.
+    pub fn from_lovelace(lovelace: Lovelace) -> Self {
+        Self { lovelace, assets: BTreeMap::new() }
+    }
+
+    /// Sum a list of amounts as returned by the API, `unit`s can repeat.
+    pub fn from_amounts(amounts: &[Amount]) -> Result<Self, ParseError> {
+        let mut value = Self::new();
+
+        for amount in amounts {
+            let quantity = amount.parsed_quantity()?;
+            let overflow = || ParseError::new("value", "sum of quantities overflows");
+
+            match amount.asset_unit()? {
+                AssetUnit::Lovelace => {
+                    value.lovelace =
+                        value.lovelace.checked_add(Lovelace(quantity)).ok_or_else(overflow)?;
+                }
+                AssetUnit::Asset(asset) => {
+                    value = value.with_asset(asset, quantity).ok_or_else(overflow)?;
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    /// Add an asset quantity, `None` on overflow.
+    pub fn with_asset(mut self, asset: AssetId, quantity: u64) -> Option<Self> {
+        if quantity != 0 {
+            let current = self.assets.entry(asset).or_insert(0);
+            *current = current.checked_add(quantity)?;
+        }
+        Some(self)
+    }
+
+    /// Amount of lovelaces.
+    pub fn lovelace(&self) -> Lovelace {
+        self.lovelace
+    }
+
+    /// Quantity of a native asset, zero if absent.
+    pub fn quantity_of(&self, asset: &AssetId) -> u64 {
+        self.assets.get(asset).copied().unwrap_or(0)
+    }
+
+    /// Iterate through the native assets, sorted by policy ID and asset name.
+    pub fn assets(&self) -> impl Iterator<Item = (&AssetId, u64)> {
+        self.assets.iter().map(|(asset, quantity)| (asset, *quantity))
+    }
+
+    /// Whether the value has no native assets.
+    pub fn is_ada_only(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Whether the value has no lovelaces and no native assets.
+    pub fn is_zero(&self) -> bool {
+        self.lovelace.0 == 0 && self.assets.is_empty()
+    }
+
+    /// Checked addition, `None` on overflow.
+    pub fn checked_add(&self, other: &Value) -> Option<Value> {
+        let mut result = self.clone();
+        result.lovelace = result.lovelace.checked_add(other.lovelace)?;
+
+        for (asset, quantity) in other.assets() {
+            result = result.with_asset(asset.clone(), quantity)?;
+        }
+        Some(result)
+    }
+
+    /// Checked subtraction, `None` if `self` doesn't contain all of `other`.
+    pub fn checked_sub(&self, other: &Value) -> Option<Value> {
+        let mut result = self.clone();
+        result.lovelace = result.lovelace.checked_sub(other.lovelace)?;
+
+        for (asset, quantity) in other.assets() {
+            let current = result.assets.get_mut(asset)?;
+            *current = current.checked_sub(quantity)?;
+            if *current == 0 {
+                result.assets.remove(asset);
+            }
+        }
+        Some(result)
+    }
+
+    /// Convert back to the list of amounts used by the API, lovelaces first.
+    pub fn to_amounts(&self) -> Vec<Amount> {
+        let lovelace =
+            Amount { unit: LOVELACE_UNIT.to_owned(), quantity: self.lovelace.0.to_string() };
+        let assets = self.assets().map(|(asset, quantity)| Amount {
+            unit: asset.to_string(),
+            quantity: quantity.to_string(),
+        });
+
+        std::iter::once(lovelace).chain(assets).collect()
+    }
+}
+
+impl From<Lovelace> for Value {
+    fn from(lovelace: Lovelace) -> Self {
+        Self::from_lovelace(lovelace)
+    }
+}
+
+/// Shows the ADA amount followed by each asset unit, e.g. `1.500000 ADA + 12 <unit>`.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.lovelace)?;
+        for (asset, quantity) in self.assets() {
+            write!(f, " + {} {}", quantity, asset)?;
+        }
+        Ok(())
+    }
+}
+
+impl Amount {
+    /// Parse the `unit` field.
+    pub fn asset_unit(&self) -> Result<AssetUnit, ParseError> {
+        self.unit.parse()
+    }
+
+    /// Parse the `quantity` field.
+    pub fn parsed_quantity(&self) -> Result<u64, ParseError> {
+        parse_quantity(&self.quantity)
+    }
+
+    /// Lovelaces of this amount, `None` if the unit is a native asset.
+    pub fn lovelace(&self) -> Option<Result<Lovelace, ParseError>> {
+        (self.unit == LOVELACE_UNIT).then(|| self.quantity.parse())
+    }
+}
+
+impl AssetDetails {
+    /// Whether the [`fingerprint`](AssetDetails::fingerprint) field matches the one computed
+    /// locally from the asset unit.
+    pub fn verify_fingerprint(&self) -> Result<bool, ParseError> {
+        Ok(self.asset_id()?.fingerprint() == self.fingerprint)
+    }
+}
+
+fn parse_quantity(text: &str) -> Result<u64, ParseError> {
+    text.parse().map_err(|reason| ParseError::new("quantity", format!("'{}': {}", text, reason)))
+}
+
+// Defines accessors that parse lovelace `String` fields into `Lovelace`.
+macro_rules! lovelace_accessors {
+    ($($type:ident { $($field:ident),* $(,)? })*) => {
+        $(
+            impl $type {
+                $(
+                    paste::paste! {
+                        #[doc = concat!(
+                            "Parse [`", stringify!($field), "`](", stringify!($type), "::",
+                            stringify!($field), ") into [`Lovelace`]."
+                        )]
+                        pub fn [<$field _lovelace>](&self) -> Result<Lovelace, ParseError> {
+                            self.$field.parse()
+                        }
+                    }
+                )*
+            }
+        )*
+    };
+}
+
+// Defines accessors that sum `Vec<Amount>` fields into a `Value`.
+macro_rules! value_accessors {
+    ($($type:ident { $($field:ident => $method:ident),* $(,)? })*) => {
+        $(
+            impl $type {
+                $(
+                    #[doc = concat!(
+                        "Sum [`", stringify!($field), "`](", stringify!($type), "::",
+                        stringify!($field), ") into a [`Value`]."
+                    )]
+                    pub fn $method(&self) -> Result<Value, ParseError> {
+                        Value::from_amounts(&self.$field)
+                    }
+                )*
+            }
+        )*
+    };
+}
+
+// Defines `asset_id` for response types with an asset unit field.
+macro_rules! asset_id_accessors {
+    ($($type:ident { $field:ident })*) => {
+        $(
+            impl $type {
+                #[doc = concat!(
+                    "Parse [`", stringify!($field), "`](", stringify!($type), "::",
+                    stringify!($field), ") into an [`AssetId`], to get its fingerprint, label and",
+                    " readable name.\n\nFails for lovelaces."
+                )]
+                pub fn asset_id(&self) -> Result<AssetId, ParseError> {
+                    self.$field.parse()
+                }
+            }
+        )*
+    };
+}
+
+asset_id_accessors! {
+    Amount { unit }
+    Asset { asset }
+    AssetDetails { asset }
+}
+
+lovelace_accessors! {
+    Transaction { fees, deposit }
+    Account {
+        controlled_amount,
+        rewards_sum,
+        withdrawals_sum,
+        reserves_sum,
+        treasury_sum,
+        withdrawable_amount,
+    }
+    Pool { live_stake, active_stake, declared_pledge, live_pledge, fixed_cost }
+}
+
+value_accessors! {
+    Transaction { output_amount => output_value }
+    Address { amount => value }
+    AddressTotal { received_sum => received_value, sent_sum => sent_value }
+    AddressUtxo { amount => value }
+    TransactionUtxosInput { amount => value }
+    TransactionUtxosOutput { amount => value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIT: &str = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a76e7574636f696e";
+
+    fn amount(unit: &str, quantity: &str) -> Amount {
+        Amount { unit: unit.to_owned(), quantity: quantity.to_owned() }
+    }
+
+    #[test]
+    fn test_lovelace_display() {
+        assert_eq!(Lovelace(0).to_string(), "0.000000 ADA");
+        assert_eq!(Lovelace(1_500_000).to_string(), "1.500000 ADA");
+        assert_eq!(Lovelace(45_000_000_000_000_001).to_string(), "45000000000.000001 ADA");
+    }
+
+    #[test]
+    fn test_asset_id_parsing() {
+        let asset: AssetId = UNIT.parse().unwrap();
+        assert_eq!(asset.asset_name_bytes(), b"nutcoin");
+
+        // Empty asset name
+        let policy_only = &UNIT[..56];
+        assert_eq!(policy_only.parse::<AssetId>().unwrap().asset_name, "");
+
+        assert!("lovelace".parse::<AssetId>().is_err());
+        assert!(UNIT[..60].replace('b', "x").parse::<AssetId>().is_err());
+        assert!(format!("{}0", UNIT).parse::<AssetId>().is_err());
+        assert!(format!("{}{}", policy_only, "00".repeat(33)).parse::<AssetId>().is_err());
+    }
+
+    #[test]
+    fn test_asset_fingerprint() {
+        // Test vectors from CIP-14
+        let cases = [
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "",
+                "asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3",
+            ),
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc37e",
+                "",
+                "asset1nl0puwxmhas8fawxp8nx4e2q3wekg969n2auw3",
+            ),
+            (
+                "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+                "504154415445",
+                "asset1hv4p5tv2a837mzqrst04d0dcptdjmluqvdx9k3",
+            ),
+            (
+                "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+                "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209",
+                "asset17jd78wukhtrnmjh3fngzasxm8rck0l2r4hhyyt",
+            ),
+        ];
+        for (policy_id, asset_name, fingerprint) in cases {
+            assert_eq!(AssetId::new(policy_id, asset_name).unwrap().fingerprint(), fingerprint);
+        }
+    }
+
+    #[test]
+    fn test_asset_labels() {
+        let prefixes = [
+            (AssetLabel::ReferenceNft, "000643b0"),
+            (AssetLabel::Nft, "000de140"),
+            (AssetLabel::Ft, "0014df10"),
+            (AssetLabel::Rft, "001bc280"),
+        ];
+        for (label, prefix) in prefixes {
+            assert_eq!(label.prefix_hex(), prefix);
+            let asset = AssetId::new(&UNIT[..56], &format!("{}4e7574", prefix)).unwrap();
+            assert_eq!(asset.label(), Some(label));
+            assert_eq!(asset.asset_name_utf8().unwrap(), "Nut");
+
+            let reference = asset.with_label(AssetLabel::ReferenceNft).unwrap();
+            assert_eq!(reference.asset_name, "000643b04e7574");
+        }
+
+        // An unlabelled 32 bytes name has no room for a label
+        let asset = AssetId::new(&UNIT[..56], &"ab".repeat(32)).unwrap();
+        assert!(asset.with_label(AssetLabel::Nft).is_err());
+        let asset = AssetId::new(&UNIT[..56], &"ab".repeat(28)).unwrap();
+        assert_eq!(asset.with_label(AssetLabel::Nft).unwrap().asset_name.len(), 64);
+
+        let asset: AssetId = UNIT.parse().unwrap();
+        assert_eq!(asset.label(), None);
+        assert_eq!(asset.asset_name_utf8().unwrap(), "nutcoin");
+
+        // Bad checksum, and names that are not text
+        assert_eq!(AssetId::new(&UNIT[..56], "000de150").unwrap().label(), None);
+        assert_eq!(AssetId::new(&UNIT[..56], "").unwrap().asset_name_utf8(), None);
+        assert_eq!(AssetId::new(&UNIT[..56], "00ff").unwrap().asset_name_utf8(), None);
+    }
+
+    #[test]
+    fn test_value_from_amounts() {
+        let amounts = [
+            amount("lovelace", "1000"),
+            amount(UNIT, "5"),
+            amount("lovelace", "500"),
+            amount(UNIT, "2"),
+        ];
+        let value = Value::from_amounts(&amounts).unwrap();
+
+        assert_eq!(value.lovelace(), Lovelace(1500));
+        assert_eq!(value.quantity_of(&UNIT.parse().unwrap()), 7);
+        assert_eq!(value.to_string(), format!("0.001500 ADA + 7 {}", UNIT));
+        assert_eq!(Value::from_amounts(&value.to_amounts()).unwrap(), value);
+
+        assert!(Value::from_amounts(&[amount("lovelace", "-1")]).is_err());
+        assert!(Value::from_amounts(&[amount("nutcoin", "1")]).is_err());
+    }
+
+    #[test]
+    fn test_value_checked_arithmetic() {
+        let asset: AssetId = UNIT.parse().unwrap();
+        let small = Value::from_lovelace(Lovelace(10)).with_asset(asset.clone(), 3).unwrap();
+        let big = Value::from_lovelace(Lovelace(20)).with_asset(asset.clone(), 3).unwrap();
+
+        let difference = big.checked_sub(&small).unwrap();
+        assert_eq!(difference, Value::from_lovelace(Lovelace(10)));
+        assert!(difference.is_ada_only());
+        assert_eq!(small.checked_add(&difference).unwrap(), big);
+
+        assert_eq!(small.checked_sub(&big), None);
+        assert_eq!(Value::new().checked_sub(&Value::new().with_asset(asset, 1).unwrap()), None);
+        assert_eq!(Value::from_lovelace(Lovelace(u64::MAX)).checked_add(&small), None);
+    }
+}